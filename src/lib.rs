@@ -1,7 +1,9 @@
 pub mod prelude;
-pub use prelude::IterExtra;
+pub mod stats;
+pub use prelude::{IterExtra, TryIterExtra};
 
 #[cfg(test)]
+#[allow(clippy::useless_vec)]
 mod tests {
     use crate::prelude::*;
 
@@ -271,4 +273,3668 @@ mod tests {
         let deltas = arr.iter().deltas_by_key(|x| *x);
         assert_eq!(deltas.collect::<Vec<usize>>(), vec![0]);
     }
+
+    #[test]
+    fn windows_ref_basic() {
+        let mut windows = vec![1, 2, 3, 4, 5].into_iter().windows_ref(2);
+        let mut collected = Vec::new();
+        while let Some(window) = windows.next_window() {
+            collected.push(window.to_vec());
+        }
+        assert_eq!(
+            collected,
+            vec![vec![1, 2], vec![2, 3], vec![3, 4], vec![4, 5]]
+        );
+    }
+
+    #[test]
+    fn windows_ref_shorter_than_window() {
+        let mut windows = vec![1, 2].into_iter().windows_ref(3);
+        assert_eq!(windows.next_window(), None);
+        assert_eq!(windows.next_window(), None);
+    }
+
+    #[test]
+    fn windows_ref_exact_length() {
+        let mut windows = vec![1, 2, 3].into_iter().windows_ref(3);
+        assert_eq!(windows.next_window(), Some(&[1, 2, 3][..]));
+        assert_eq!(windows.next_window(), None);
+    }
+
+    #[test]
+    fn windows_ref_non_clone_items() {
+        struct Big(u32);
+        let mut windows = (0..100).map(Big).windows_ref(10);
+        let mut sums = Vec::new();
+        while let Some(window) = windows.next_window() {
+            sums.push(window.iter().map(|b| b.0).sum::<u32>());
+        }
+        assert_eq!(sums.len(), 91);
+        assert_eq!(sums[0], 45);
+        assert_eq!(sums[90], (90..100).sum::<u32>());
+    }
+
+    #[test]
+    #[should_panic(expected = "window size must be non-zero")]
+    fn windows_ref_zero_size() {
+        let _ = vec![1, 2, 3].into_iter().windows_ref(0);
+    }
+
+    #[test]
+    fn batching_pairs() {
+        let arr = vec![1, 2, 3, 4, 5];
+        let pairs = arr
+            .into_iter()
+            .batching(|it| it.next().map(|x| (x, it.next())))
+            .collect::<Vec<_>>();
+        assert_eq!(pairs, vec![(1, Some(2)), (3, Some(4)), (5, None)]);
+    }
+
+    #[test]
+    fn batching_empty() {
+        let arr: Vec<i32> = vec![];
+        let batches = arr
+            .into_iter()
+            .batching(|it| it.next().map(|x| vec![x]))
+            .collect::<Vec<_>>();
+        assert!(batches.is_empty());
+    }
+
+    #[test]
+    fn batching_length_prefixed_frames() {
+        // Each frame starts with its length
+        let arr = vec![2, 10, 20, 1, 30, 3, 40, 50, 60];
+        let frames = arr
+            .into_iter()
+            .batching(|it| {
+                let len = it.next()?;
+                Some(it.take(len).collect::<Vec<_>>())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(frames, vec![vec![10, 20], vec![30], vec![40, 50, 60]]);
+    }
+
+    #[test]
+    fn with_position_basic() {
+        let arr = vec![1, 2, 3, 4];
+        let positions = arr.iter().with_position().collect::<Vec<_>>();
+        assert_eq!(
+            positions,
+            vec![
+                (Position::First, &1),
+                (Position::Middle, &2),
+                (Position::Middle, &3),
+                (Position::Last, &4)
+            ]
+        );
+    }
+
+    #[test]
+    fn with_position_empty() {
+        let arr: Vec<i32> = vec![];
+        assert_eq!(arr.iter().with_position().next(), None);
+    }
+
+    #[test]
+    fn with_position_single() {
+        let arr = vec![42];
+        let positions = arr.iter().with_position().collect::<Vec<_>>();
+        assert_eq!(positions, vec![(Position::Only, &42)]);
+    }
+
+    #[test]
+    fn with_position_two_elements() {
+        let arr = vec![1, 2];
+        let positions = arr.iter().with_position().collect::<Vec<_>>();
+        assert_eq!(positions, vec![(Position::First, &1), (Position::Last, &2)]);
+    }
+
+    #[test]
+    fn with_position_separator_logic() {
+        let words = vec!["a", "b", "c"];
+        let mut out = String::new();
+        for (position, word) in words.iter().with_position() {
+            out.push_str(word);
+            match position {
+                Position::First | Position::Middle => out.push_str(", "),
+                Position::Last | Position::Only => out.push('.'),
+            }
+        }
+        assert_eq!(out, "a, b, c.");
+    }
+
+    #[test]
+    fn intersperse_basic() {
+        let arr = vec![1, 2, 3];
+        let result = IterExtra::intersperse(arr.into_iter(), 0).collect::<Vec<_>>();
+        assert_eq!(result, vec![1, 0, 2, 0, 3]);
+    }
+
+    #[test]
+    fn intersperse_empty() {
+        let arr: Vec<i32> = vec![];
+        let result = IterExtra::intersperse(arr.into_iter(), 0).collect::<Vec<_>>();
+        assert_eq!(result, vec![]);
+    }
+
+    #[test]
+    fn intersperse_single() {
+        let arr = vec![1];
+        let result = IterExtra::intersperse(arr.into_iter(), 0).collect::<Vec<_>>();
+        assert_eq!(result, vec![1]);
+    }
+
+    #[test]
+    fn intersperse_with_counter() {
+        let arr = vec!["a", "b", "c"];
+        let mut count = 0;
+        let result = IterExtra::intersperse_with(arr.into_iter(), || {
+            count += 1;
+            "-"
+        })
+        .collect::<String>();
+        assert_eq!(result, "a-b-c");
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn intersperse_with_empty() {
+        let arr: Vec<String> = vec![];
+        let result =
+            IterExtra::intersperse_with(arr.into_iter(), || ",".to_string()).collect::<Vec<_>>();
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn join_basic() {
+        let arr = vec![1, 2, 3];
+        assert_eq!(arr.iter().join(", "), "1, 2, 3");
+    }
+
+    #[test]
+    fn join_empty() {
+        let arr: Vec<i32> = vec![];
+        assert_eq!(arr.iter().join(", "), "");
+    }
+
+    #[test]
+    fn join_single() {
+        let arr = vec!["only"];
+        assert_eq!(arr.iter().join(", "), "only");
+    }
+
+    #[test]
+    fn join_empty_separator() {
+        let arr = vec!['a', 'b', 'c'];
+        assert_eq!(arr.iter().join(""), "abc");
+    }
+
+    #[test]
+    fn join_map_basic() {
+        let arr = vec![1.5, 2.25, 3.0];
+        assert_eq!(arr.iter().join_map(" | ", |x| x * 2.0), "3 | 4.5 | 6");
+    }
+
+    #[test]
+    fn join_map_empty() {
+        let arr: Vec<i32> = vec![];
+        assert_eq!(arr.iter().join_map(",", |x| x + 1), "");
+    }
+
+    #[test]
+    fn format_with_basic() {
+        let arr = vec![1, 2, 3];
+        let formatted = arr.iter().format_with(", ", |x, f| write!(f, "<{x}>"));
+        assert_eq!(formatted.to_string(), "<1>, <2>, <3>");
+    }
+
+    #[test]
+    fn format_with_empty() {
+        let arr: Vec<i32> = vec![];
+        let formatted = arr.iter().format_with(", ", |x, f| write!(f, "{x}"));
+        assert_eq!(format!("({formatted})"), "()");
+    }
+
+    #[test]
+    fn format_with_respects_formatter_errors() {
+        use std::fmt::Write;
+
+        struct FailingWriter;
+        impl Write for FailingWriter {
+            fn write_str(&mut self, _: &str) -> std::fmt::Result {
+                Err(std::fmt::Error)
+            }
+        }
+
+        let arr = vec![1, 2];
+        let formatted = arr.iter().format_with(",", |x, f| write!(f, "{x}"));
+        assert!(write!(FailingWriter, "{formatted}").is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "FormatWith can only be formatted once")]
+    fn format_with_twice() {
+        let arr = vec![1, 2];
+        let formatted = arr.iter().format_with(",", |x, f| write!(f, "{x}"));
+        let _ = formatted.to_string();
+        let _ = formatted.to_string();
+    }
+
+    #[test]
+    fn zip_eq_same_length() {
+        let a = vec![1, 2, 3];
+        let b = vec![4, 5, 6];
+        let zipped = a.iter().zip_eq(b.iter()).collect::<Vec<_>>();
+        assert_eq!(zipped, vec![(&1, &4), (&2, &5), (&3, &6)]);
+    }
+
+    #[test]
+    fn zip_eq_empty() {
+        let a: Vec<i32> = vec![];
+        let b: Vec<i32> = vec![];
+        assert_eq!(a.iter().zip_eq(b.iter()).count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "zip_eq")]
+    fn zip_eq_left_shorter() {
+        let a = vec![1, 2];
+        let b = vec![4, 5, 6];
+        let _ = a.iter().zip_eq(b.iter()).collect::<Vec<_>>();
+    }
+
+    #[test]
+    #[should_panic(expected = "zip_eq")]
+    fn zip_eq_right_shorter() {
+        let a = vec![1, 2, 3];
+        let b = vec![4];
+        let _ = a.iter().zip_eq(b.iter()).collect::<Vec<_>>();
+    }
+
+    #[test]
+    fn try_zip_eq_same_length() {
+        let a = vec![1, 2];
+        let b = vec!['x', 'y'];
+        let zipped = a.into_iter().try_zip_eq(b).collect::<Vec<_>>();
+        assert_eq!(zipped, vec![Ok((1, 'x')), Ok((2, 'y'))]);
+    }
+
+    #[test]
+    fn try_zip_eq_left_shorter() {
+        let a = vec![1];
+        let b = vec!['x', 'y', 'z'];
+        let zipped = a.into_iter().try_zip_eq(b).collect::<Vec<_>>();
+        assert_eq!(
+            zipped,
+            vec![
+                Ok((1, 'x')),
+                Err(ZipEqError {
+                    index: 1,
+                    left_exhausted: true
+                })
+            ]
+        );
+    }
+
+    #[test]
+    fn try_zip_eq_stops_after_error() {
+        let a = vec![1, 2, 3];
+        let b: Vec<i32> = vec![];
+        let mut zipped = a.into_iter().try_zip_eq(b);
+        assert!(zipped.next().unwrap().is_err());
+        assert_eq!(zipped.next(), None);
+    }
+
+    #[test]
+    fn zip_with_basic() {
+        let a = vec![1, 2, 3];
+        let b = vec![10, 20, 30];
+        let sums = a
+            .iter()
+            .zip_with(b.iter(), |x, y| x + y)
+            .collect::<Vec<_>>();
+        assert_eq!(sums, vec![11, 22, 33]);
+    }
+
+    #[test]
+    fn zip_with_different_lengths() {
+        let a = vec![1, 2, 3, 4];
+        let b = vec![10, 20];
+        let sums = a
+            .iter()
+            .zip_with(b.iter(), |x, y| x + y)
+            .collect::<Vec<_>>();
+        assert_eq!(sums, vec![11, 22]);
+    }
+
+    #[test]
+    fn zip_with_different_types() {
+        let names = vec!["a", "b"];
+        let counts = vec![2, 3];
+        let repeated = names
+            .iter()
+            .zip_with(counts, |name, count| name.repeat(count))
+            .collect::<Vec<_>>();
+        assert_eq!(repeated, vec!["aa", "bbb"]);
+    }
+
+    #[test]
+    fn zip_with_size_hint() {
+        let zipped = (0..5).zip_with(0..3, |x, y| x * y);
+        assert_eq!(zipped.size_hint(), (3, Some(3)));
+    }
+
+    #[test]
+    fn interleave_equal_lengths() {
+        let a = vec![1, 3, 5];
+        let b = vec![2, 4, 6];
+        let merged = a.into_iter().interleave(b).collect::<Vec<_>>();
+        assert_eq!(merged, vec![1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn interleave_left_longer() {
+        let a = vec![1, 3, 5, 7];
+        let b = vec![2];
+        let merged = a.into_iter().interleave(b).collect::<Vec<_>>();
+        assert_eq!(merged, vec![1, 2, 3, 5, 7]);
+    }
+
+    #[test]
+    fn interleave_right_longer() {
+        let a = vec![1];
+        let b = vec![2, 4, 6];
+        let merged = a.into_iter().interleave(b).collect::<Vec<_>>();
+        assert_eq!(merged, vec![1, 2, 4, 6]);
+    }
+
+    #[test]
+    fn interleave_empty() {
+        let a: Vec<i32> = vec![];
+        let b = vec![1, 2];
+        assert_eq!(a.into_iter().interleave(b).collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn interleave_size_hint() {
+        let merged = (0..3).interleave(10..15);
+        assert_eq!(merged.size_hint(), (8, Some(8)));
+    }
+
+    #[test]
+    fn interleave_shortest_left_longer() {
+        let a = vec![1, 3, 5];
+        let b = vec![2];
+        let merged = a.into_iter().interleave_shortest(b).collect::<Vec<_>>();
+        assert_eq!(merged, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn interleave_shortest_right_longer() {
+        let a = vec![1];
+        let b = vec![2, 4, 6];
+        let merged = a.into_iter().interleave_shortest(b).collect::<Vec<_>>();
+        assert_eq!(merged, vec![1, 2]);
+    }
+
+    #[test]
+    fn interleave_shortest_left_empty() {
+        let a: Vec<i32> = vec![];
+        let b = vec![2, 4];
+        let merged = a.into_iter().interleave_shortest(b).collect::<Vec<_>>();
+        assert_eq!(merged, vec![]);
+    }
+
+    #[test]
+    fn kmerge_basic() {
+        let sources = vec![vec![1, 5, 9], vec![2, 6], vec![0, 3, 4, 10]];
+        let merged = sources.into_iter().kmerge().collect::<Vec<_>>();
+        assert_eq!(merged, vec![0, 1, 2, 3, 4, 5, 6, 9, 10]);
+    }
+
+    #[test]
+    fn kmerge_empty() {
+        let sources: Vec<Vec<i32>> = vec![];
+        assert_eq!(sources.into_iter().kmerge().next(), None);
+    }
+
+    #[test]
+    fn kmerge_with_empty_sources() {
+        let sources = vec![vec![], vec![2, 3], vec![], vec![1]];
+        let merged = sources.into_iter().kmerge().collect::<Vec<_>>();
+        assert_eq!(merged, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn kmerge_single_source() {
+        let sources = vec![vec![1, 2, 3]];
+        let merged = sources.into_iter().kmerge().collect::<Vec<_>>();
+        assert_eq!(merged, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn kmerge_duplicates_in_source_order() {
+        let sources = vec![vec![(1, 'a'), (2, 'a')], vec![(1, 'b'), (2, 'b')]];
+        let merged = sources
+            .into_iter()
+            .kmerge_by_partial_key(|(x, _)| *x)
+            .collect::<Vec<_>>();
+        assert_eq!(merged, vec![(1, 'a'), (1, 'b'), (2, 'a'), (2, 'b')]);
+    }
+
+    #[test]
+    fn kmerge_many_sources() {
+        let sources = (0..50)
+            .map(|offset| (0..20).map(move |i| i * 50 + offset))
+            .collect::<Vec<_>>();
+        let merged = sources.into_iter().kmerge().collect::<Vec<_>>();
+        assert_eq!(merged, (0..1000).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn kmerge_size_hint() {
+        let sources = vec![vec![1, 2], vec![3], vec![4, 5, 6]];
+        let merged = sources.into_iter().kmerge();
+        assert_eq!(merged.size_hint(), (6, Some(6)));
+    }
+
+    #[test]
+    fn kmerge_by_partial_key_descending() {
+        let sources = vec![vec![9.0, 4.0, 1.0], vec![8.0, 2.0]];
+        let merged = sources
+            .into_iter()
+            .kmerge_by_partial_key(|&x| -x)
+            .collect::<Vec<_>>();
+        assert_eq!(merged, vec![9.0, 8.0, 4.0, 2.0, 1.0]);
+    }
+
+    #[test]
+    fn kmerge_by_partial_key_references() {
+        let a = vec!["a", "ccc"];
+        let b = vec!["bb", "dddd"];
+        let merged = vec![a.iter(), b.iter()]
+            .into_iter()
+            .kmerge_by_partial_key(|s| s.len())
+            .collect::<Vec<_>>();
+        assert_eq!(merged, vec![&"a", &"bb", &"ccc", &"dddd"]);
+    }
+
+    #[test]
+    fn merge_join_by_basic() {
+        let a = vec![1, 3, 5];
+        let b = vec![2, 3, 4, 5, 6];
+        let joined = a
+            .into_iter()
+            .merge_join_by(b, |x, y| x.cmp(y))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            joined,
+            vec![
+                EitherOrBoth::Left(1),
+                EitherOrBoth::Right(2),
+                EitherOrBoth::Both(3, 3),
+                EitherOrBoth::Right(4),
+                EitherOrBoth::Both(5, 5),
+                EitherOrBoth::Right(6)
+            ]
+        );
+    }
+
+    #[test]
+    fn merge_join_by_empty_sides() {
+        let a: Vec<i32> = vec![];
+        let b = vec![1, 2];
+        let joined = a
+            .iter()
+            .merge_join_by(b.iter(), |x, y| x.cmp(y))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            joined,
+            vec![EitherOrBoth::Right(&1), EitherOrBoth::Right(&2)]
+        );
+
+        let joined = b
+            .iter()
+            .merge_join_by(a.iter(), |x, y| x.cmp(y))
+            .collect::<Vec<_>>();
+        assert_eq!(joined, vec![EitherOrBoth::Left(&1), EitherOrBoth::Left(&2)]);
+    }
+
+    #[test]
+    fn merge_join_by_different_types() {
+        let ids = vec![1u32, 2, 3];
+        let names = vec![("2", "bob"), ("3", "carol")];
+        let joined = ids
+            .iter()
+            .merge_join_by(names.iter(), |id, (key, _)| {
+                (*id).cmp(&key.parse::<u32>().unwrap())
+            })
+            .filter_map(|entry| match entry {
+                EitherOrBoth::Both(id, (_, name)) => Some((*id, *name)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(joined, vec![(2, "bob"), (3, "carol")]);
+    }
+
+    #[test]
+    fn merge_join_by_duplicates() {
+        let a = vec![1, 1, 2];
+        let b = vec![1, 2, 2];
+        let joined = a
+            .into_iter()
+            .merge_join_by(b, |x, y| x.cmp(y))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            joined,
+            vec![
+                EitherOrBoth::Both(1, 1),
+                EitherOrBoth::Left(1),
+                EitherOrBoth::Both(2, 2),
+                EitherOrBoth::Right(2)
+            ]
+        );
+    }
+
+    #[test]
+    fn tuple_combinations_pairs() {
+        let arr = vec![1, 2, 3, 4];
+        let pairs = arr.iter().tuple_combinations().collect::<Vec<(_, _)>>();
+        assert_eq!(
+            pairs,
+            vec![(&1, &2), (&1, &3), (&1, &4), (&2, &3), (&2, &4), (&3, &4)]
+        );
+    }
+
+    #[test]
+    fn tuple_combinations_triples_count() {
+        let triples = (0..10).tuple_combinations::<(_, _, _)>().count();
+        assert_eq!(triples, 120);
+    }
+
+    #[test]
+    fn tuple_combinations_quadruples() {
+        let quads = (0..5)
+            .tuple_combinations::<(_, _, _, _)>()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            quads,
+            vec![
+                (0, 1, 2, 3),
+                (0, 1, 2, 4),
+                (0, 1, 3, 4),
+                (0, 2, 3, 4),
+                (1, 2, 3, 4)
+            ]
+        );
+    }
+
+    #[test]
+    fn tuple_combinations_too_short() {
+        let arr = vec![1, 2];
+        assert_eq!(arr.iter().tuple_combinations::<(_, _, _)>().next(), None);
+
+        let empty: Vec<i32> = vec![];
+        assert_eq!(empty.iter().tuple_combinations::<(_, _)>().next(), None);
+    }
+
+    #[test]
+    fn tuple_combinations_exact_arity() {
+        let arr = vec!['a', 'b'];
+        let mut pairs = arr.into_iter().tuple_combinations::<(_, _)>();
+        assert_eq!(pairs.next(), Some(('a', 'b')));
+        assert_eq!(pairs.next(), None);
+        assert_eq!(pairs.next(), None);
+    }
+
+    #[test]
+    fn sorted_by_partial_key_basic() {
+        let arr = vec![3.2, 1.5, 2.8, 0.9, -1.0];
+        let sorted = arr
+            .iter()
+            .sorted_by_partial_key(|&&x| x)
+            .collect::<Vec<_>>();
+        assert_eq!(sorted, vec![&-1.0, &0.9, &1.5, &2.8, &3.2]);
+    }
+
+    #[test]
+    fn sorted_by_partial_key_empty() {
+        let arr: Vec<f64> = vec![];
+        assert_eq!(arr.iter().sorted_by_partial_key(|&&x| x).next(), None);
+    }
+
+    #[test]
+    fn sorted_by_partial_key_stable() {
+        let arr = vec![(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd')];
+        let sorted = arr
+            .into_iter()
+            .sorted_by_partial_key(|(x, _)| *x)
+            .collect::<Vec<_>>();
+        assert_eq!(sorted, vec![(1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')]);
+    }
+
+    #[test]
+    fn sorted_by_partial_key_with_nan_does_not_panic() {
+        let arr = (0..100)
+            .map(|i| {
+                if i % 7 == 0 {
+                    f64::NAN
+                } else {
+                    (i * 37 % 101) as f64
+                }
+            })
+            .collect::<Vec<_>>();
+        let sorted = arr
+            .iter()
+            .sorted_by_partial_key(|&&x| x)
+            .collect::<Vec<_>>();
+        assert_eq!(sorted.len(), 100);
+        assert!(sorted[..85].is_sorted());
+        assert!(sorted[85..].iter().all(|x| x.is_nan()));
+    }
+
+    #[test]
+    fn sorted_by_partial_key_nan_keys_go_last_in_input_order() {
+        let arr = vec![
+            (2.0, 'a'),
+            (1.0, 'b'),
+            (f64::NAN, 'c'),
+            (0.0, 'd'),
+            (3.0, 'e'),
+            (f64::NAN, 'f'),
+        ];
+        let order = arr
+            .into_iter()
+            .sorted_by_partial_key(|(k, _)| *k)
+            .map(|(_, c)| c)
+            .collect::<String>();
+        assert_eq!(order, "dbaecf");
+    }
+
+    #[test]
+    fn sorted_by_partial_key_without_nan_is_sorted() {
+        let arr = (0..257)
+            .map(|i| (i * 73 % 257) as f64 / 3.0)
+            .collect::<Vec<_>>();
+        let sorted = arr
+            .into_iter()
+            .sorted_by_partial_key(|&x| x)
+            .collect::<Vec<_>>();
+        assert!(sorted.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(sorted.len(), 257);
+    }
+
+    #[test]
+    fn sorted_stream_by_partial_key_full() {
+        let arr = (0..101).map(|i| (i * 37 % 101) as f64).collect::<Vec<_>>();
+        let sorted = arr
+            .into_iter()
+            .sorted_stream_by_partial_key(|&x| x)
+            .collect::<Vec<_>>();
+        assert_eq!(sorted, (0..101).map(|i| i as f64).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn sorted_stream_by_partial_key_take() {
+        let arr = vec![5, 3, 9, 1, 7];
+        let smallest = arr
+            .iter()
+            .sorted_stream_by_partial_key(|&&x| x)
+            .take(2)
+            .collect::<Vec<_>>();
+        assert_eq!(smallest, vec![&1, &3]);
+    }
+
+    #[test]
+    fn sorted_stream_by_partial_key_empty() {
+        let arr: Vec<f64> = vec![];
+        let mut sorted = arr.iter().sorted_stream_by_partial_key(|&&x| x);
+        assert_eq!(sorted.size_hint(), (0, Some(0)));
+        assert_eq!(sorted.next(), None);
+    }
+
+    #[test]
+    fn sorted_stream_by_partial_key_ties_in_input_order() {
+        let arr = vec![(1, 'a'), (0, 'b'), (1, 'c'), (0, 'd')];
+        let sorted = arr
+            .into_iter()
+            .sorted_stream_by_partial_key(|(x, _)| *x)
+            .collect::<Vec<_>>();
+        assert_eq!(sorted, vec![(0, 'b'), (0, 'd'), (1, 'a'), (1, 'c')]);
+    }
+
+    #[test]
+    fn sorted_stream_by_partial_key_size_hint() {
+        let mut sorted = vec![3, 1, 2]
+            .into_iter()
+            .sorted_stream_by_partial_key(|&x| x);
+        assert_eq!(sorted.size_hint(), (3, Some(3)));
+        sorted.next();
+        assert_eq!(sorted.size_hint(), (2, Some(2)));
+    }
+
+    #[test]
+    fn k_smallest_sorted_by_partial_key_basic() {
+        let arr = vec![5.5, 1.1, 4.4, 2.2, 3.3];
+        let smallest = arr.iter().k_smallest_sorted_by_partial_key(3, |&&x| x);
+        assert_eq!(smallest, vec![&1.1, &2.2, &3.3]);
+    }
+
+    #[test]
+    fn k_smallest_sorted_by_partial_key_k_larger_than_len() {
+        let arr = vec![3, 1, 2];
+        let smallest = arr.into_iter().k_smallest_sorted_by_partial_key(10, |&x| x);
+        assert_eq!(smallest, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn k_smallest_sorted_by_partial_key_zero() {
+        let arr = vec![3, 1, 2];
+        let smallest = arr.into_iter().k_smallest_sorted_by_partial_key(0, |&x| x);
+        assert!(smallest.is_empty());
+    }
+
+    #[test]
+    fn k_smallest_sorted_by_partial_key_empty() {
+        let arr: Vec<f64> = vec![];
+        assert!(
+            arr.iter()
+                .k_smallest_sorted_by_partial_key(3, |&&x| x)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn k_smallest_sorted_by_partial_key_ties_prefer_earlier() {
+        let arr = vec![(1, 'a'), (0, 'b'), (1, 'c'), (0, 'd'), (1, 'e')];
+        let smallest = arr
+            .into_iter()
+            .k_smallest_sorted_by_partial_key(3, |(x, _)| *x);
+        assert_eq!(smallest, vec![(0, 'b'), (0, 'd'), (1, 'a')]);
+    }
+
+    #[test]
+    fn k_smallest_sorted_by_partial_key_large() {
+        let arr = (0..1000).map(|i| (i * 383) % 1000).collect::<Vec<_>>();
+        let smallest = arr.into_iter().k_smallest_sorted_by_partial_key(5, |&x| x);
+        assert_eq!(smallest, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn median_by_partial_key_odd() {
+        let arr = vec![5.0, 1.0, 4.0, 2.0, 3.0];
+        assert_eq!(arr.iter().median_by_partial_key(|&&x| x), Some(&3.0));
+    }
+
+    #[test]
+    fn median_by_partial_key_even_returns_lower() {
+        let arr = vec![4, 1, 3, 2];
+        assert_eq!(arr.iter().median_by_partial_key(|&&x| x), Some(&2));
+    }
+
+    #[test]
+    fn median_by_partial_key_empty() {
+        let arr: Vec<f64> = vec![];
+        assert_eq!(arr.iter().median_by_partial_key(|&&x| x), None);
+    }
+
+    #[test]
+    fn median_by_partial_key_all_equal() {
+        let arr = vec![7; 1000];
+        assert_eq!(arr.iter().median_by_partial_key(|&&x| x), Some(&7));
+    }
+
+    #[test]
+    fn median_by_partial_key_with_nan() {
+        let arr = vec![f64::NAN, 1.0, f64::NAN];
+        let median = arr.iter().median_by_partial_key(|&&x| x);
+        assert!(median.is_some());
+    }
+
+    #[test]
+    fn median_partial_odd_and_even() {
+        assert_eq!(vec![9.0, 2.0, 7.0].into_iter().median_partial(), Some(7.0));
+        assert_eq!(
+            vec![9.0, 2.0, 7.0, 4.0].into_iter().median_partial(),
+            Some(5.5)
+        );
+    }
+
+    #[test]
+    fn median_partial_single_and_empty() {
+        assert_eq!(vec![42u8].into_iter().median_partial(), Some(42.0));
+        assert_eq!(Vec::<f32>::new().into_iter().median_partial(), None);
+    }
+
+    #[test]
+    fn median_partial_large() {
+        let arr = (0..1001).map(|i| ((i * 467) % 1001) as f64);
+        assert_eq!(arr.median_partial(), Some(500.0));
+    }
+
+    #[test]
+    fn quantile_by_float_key_linear() {
+        let arr = vec![1.0, 2.0, 3.0, 4.0];
+        let q = |q| {
+            arr.iter()
+                .quantile_by_float_key(q, QuantileInterpolation::Linear, |&&x| x)
+        };
+        assert_eq!(q(0.0), Some(1.0));
+        assert_eq!(q(1.0), Some(4.0));
+        assert_eq!(q(0.5), Some(2.5));
+        assert_eq!(q(0.25), Some(1.75));
+    }
+
+    #[test]
+    fn quantile_by_float_key_interpolation_modes() {
+        let arr = vec![40, 10, 30, 20];
+        let q = |interpolation| {
+            arr.iter()
+                .quantile_by_float_key(0.4, interpolation, |&&x| x)
+        };
+        assert_eq!(q(QuantileInterpolation::Lower), Some(20.0));
+        assert_eq!(q(QuantileInterpolation::Higher), Some(30.0));
+        assert_eq!(q(QuantileInterpolation::Nearest), Some(20.0));
+        assert_eq!(q(QuantileInterpolation::Midpoint), Some(25.0));
+        assert!((q(QuantileInterpolation::Linear).unwrap() - 22.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn quantile_by_float_key_nearest_rounds_up() {
+        let arr = vec![0.0, 10.0];
+        let nearest =
+            arr.iter()
+                .quantile_by_float_key(0.75, QuantileInterpolation::Nearest, |&&x| x);
+        assert_eq!(nearest, Some(10.0));
+    }
+
+    #[test]
+    fn quantile_by_float_key_empty_and_single() {
+        let empty: Vec<f64> = vec![];
+        assert_eq!(
+            empty
+                .iter()
+                .quantile_by_float_key(0.5, QuantileInterpolation::Linear, |&&x| x),
+            None
+        );
+
+        let single = vec![3.5];
+        assert_eq!(
+            single
+                .iter()
+                .quantile_by_float_key(0.9, QuantileInterpolation::Linear, |&&x| x),
+            Some(3.5)
+        );
+    }
+
+    #[test]
+    fn quantile_by_float_key_with_key_function() {
+        let requests = vec![("a", 120u32), ("b", 80), ("c", 100)];
+        let median = requests.iter().quantile_by_float_key(
+            0.5,
+            QuantileInterpolation::default(),
+            |(_, ms)| *ms,
+        );
+        assert_eq!(median, Some(100.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "quantile must be within [0, 1]")]
+    fn quantile_by_float_key_out_of_range() {
+        let arr = vec![1.0];
+        let _ = arr
+            .iter()
+            .quantile_by_float_key(1.5, QuantileInterpolation::Linear, |&&x| x);
+    }
+
+    #[test]
+    fn ranks_by_partial_key_no_ties() {
+        let arr = vec![3.0, 1.0, 2.0];
+        let ranks = arr.iter().ranks_by_partial_key(RankTies::Average, |&&x| x);
+        assert_eq!(ranks, vec![3.0, 1.0, 2.0]);
+    }
+
+    #[test]
+    fn ranks_by_partial_key_tie_modes() {
+        let arr = vec![5, 1, 5, 5, 2];
+        let ranks = |ties| arr.iter().ranks_by_partial_key(ties, |&&x| x);
+        assert_eq!(ranks(RankTies::Average), vec![4.0, 1.0, 4.0, 4.0, 2.0]);
+        assert_eq!(ranks(RankTies::Min), vec![3.0, 1.0, 3.0, 3.0, 2.0]);
+        assert_eq!(ranks(RankTies::Max), vec![5.0, 1.0, 5.0, 5.0, 2.0]);
+        assert_eq!(ranks(RankTies::Dense), vec![3.0, 1.0, 3.0, 3.0, 2.0]);
+    }
+
+    #[test]
+    fn ranks_by_partial_key_empty() {
+        let arr: Vec<f64> = vec![];
+        assert!(
+            arr.iter()
+                .ranks_by_partial_key(RankTies::Average, |&&x| x)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn ranks_by_partial_key_all_equal() {
+        let arr = vec![7.0; 4];
+        let ranks = arr.iter().ranks_by_partial_key(RankTies::Average, |&&x| x);
+        assert_eq!(ranks, vec![2.5; 4]);
+    }
+
+    #[test]
+    fn ranks_by_partial_key_with_key_function() {
+        let people = vec![("Alice", 25), ("Bob", 30), ("Charlie", 20)];
+        let ranks = people
+            .iter()
+            .ranks_by_partial_key(RankTies::Min, |(_, age)| *age);
+        assert_eq!(ranks, vec![2.0, 3.0, 1.0]);
+    }
+
+    #[test]
+    fn describe_basic() {
+        let summary = vec![1.0, 2.0, 3.0, 4.0].into_iter().describe().unwrap();
+        assert_eq!(summary.count, 4);
+        assert_eq!(summary.nan_count, 0);
+        assert_eq!(summary.min, 1.0);
+        assert_eq!(summary.max, 4.0);
+        assert_eq!(summary.mean, 2.5);
+        assert_eq!(summary.variance, 1.25);
+        assert_eq!(summary.stddev, 1.25f64.sqrt());
+    }
+
+    #[test]
+    fn describe_empty() {
+        assert_eq!(Vec::<f64>::new().into_iter().describe(), None);
+    }
+
+    #[test]
+    fn describe_all_nan() {
+        assert_eq!(vec![f64::NAN, f64::NAN].into_iter().describe(), None);
+    }
+
+    #[test]
+    fn describe_skips_nan() {
+        let summary = vec![f64::NAN, 5.0, f64::NAN]
+            .into_iter()
+            .describe()
+            .unwrap();
+        assert_eq!(summary.count, 1);
+        assert_eq!(summary.nan_count, 2);
+        assert_eq!(summary.mean, 5.0);
+        assert_eq!(summary.variance, 0.0);
+    }
+
+    #[test]
+    fn describe_integers() {
+        let summary = vec![3u32, 7, 5].into_iter().describe().unwrap();
+        assert_eq!(summary.min, 3.0);
+        assert_eq!(summary.max, 7.0);
+        assert_eq!(summary.mean, 5.0);
+    }
+
+    #[test]
+    fn describe_numerically_stable() {
+        let values = (0..1000).map(|i| 1e9 + (i % 2) as f64);
+        let summary = values.describe().unwrap();
+        assert!((summary.mean - (1e9 + 0.5)).abs() < 1e-6);
+        assert!((summary.variance - 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn mean_basic() {
+        assert_eq!(vec![1.0, 2.0, 3.0, 4.0].into_iter().mean(), Some(2.5));
+        assert_eq!(vec![1u8, 2].into_iter().mean(), Some(1.5));
+    }
+
+    #[test]
+    fn mean_empty() {
+        assert_eq!(Vec::<f32>::new().into_iter().mean(), None);
+    }
+
+    #[test]
+    fn mean_with_nan() {
+        assert!(vec![1.0, f64::NAN].into_iter().mean().unwrap().is_nan());
+    }
+
+    #[test]
+    fn variance_basic() {
+        assert_eq!(vec![1.0, 3.0].into_iter().variance(), Some(1.0));
+        assert_eq!(vec![5.0].into_iter().variance(), Some(0.0));
+    }
+
+    #[test]
+    fn variance_empty() {
+        assert_eq!(Vec::<f64>::new().into_iter().variance(), None);
+    }
+
+    #[test]
+    fn variance_large_offset() {
+        let values = (0..10_000).map(|i| 1e12 + (i % 3) as f64);
+        let variance = values.variance().unwrap();
+        assert!((variance - 2.0 / 3.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn stddev_basic() {
+        assert_eq!(vec![1.0, 3.0, 1.0, 3.0].into_iter().stddev(), Some(1.0));
+        assert_eq!(Vec::<f64>::new().into_iter().stddev(), None);
+        assert!(vec![f64::NAN].into_iter().stddev().unwrap().is_nan());
+    }
+
+    #[test]
+    fn sum_compensated_basic() {
+        assert_eq!(vec![1.0, 2.0, 3.5].into_iter().sum_compensated(), 6.5);
+        assert_eq!(vec![1u16, 2, 3].into_iter().sum_compensated(), 6.0);
+    }
+
+    #[test]
+    fn sum_compensated_empty() {
+        assert_eq!(Vec::<f64>::new().into_iter().sum_compensated(), 0.0);
+    }
+
+    #[test]
+    fn sum_compensated_infinities() {
+        assert_eq!(
+            vec![f64::INFINITY].into_iter().sum_compensated(),
+            f64::INFINITY
+        );
+        assert_eq!(
+            vec![1.0, f64::INFINITY].into_iter().sum_compensated(),
+            f64::INFINITY
+        );
+        assert_eq!(
+            vec![f64::NEG_INFINITY, 2.0].into_iter().sum_compensated(),
+            f64::NEG_INFINITY
+        );
+        assert!(
+            vec![f64::INFINITY, f64::NEG_INFINITY]
+                .into_iter()
+                .sum_compensated()
+                .is_nan()
+        );
+    }
+
+    #[test]
+    fn sum_compensated_overflow() {
+        assert_eq!(
+            vec![f64::MAX, f64::MAX].into_iter().sum_compensated(),
+            f64::INFINITY
+        );
+        assert_eq!(
+            vec![-f64::MAX, -f64::MAX, 1.0]
+                .into_iter()
+                .sum_compensated(),
+            f64::NEG_INFINITY
+        );
+    }
+
+    #[test]
+    fn sum_compensated_many_small_values() {
+        let values = std::iter::repeat_n(0.1, 10_000_000);
+        let naive = values.clone().sum::<f64>();
+        let compensated = values.sum_compensated();
+        assert!((compensated - 1_000_000.0).abs() < (naive - 1_000_000.0).abs());
+        assert!((compensated - 1_000_000.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn sum_compensated_cancellation() {
+        let values = vec![1e16, 1.0, -1e16];
+        assert_eq!(values.into_iter().sum_compensated(), 1.0);
+    }
+
+    #[test]
+    fn sum_compensated_f32() {
+        let values = std::iter::repeat_n(0.1f32, 1_000_000);
+        let compensated = values.sum_compensated();
+        assert!((compensated - 100_000.0).abs() < 1e-2);
+    }
+
+    #[test]
+    fn sum_finite_basic() {
+        assert_eq!(vec![1.0, 2.0, 3.0].into_iter().sum_finite(), (6.0, 0));
+    }
+
+    #[test]
+    fn sum_finite_skips_non_finite() {
+        let arr = vec![f64::NEG_INFINITY, 1.0, f64::NAN, 2.0, f64::INFINITY];
+        assert_eq!(arr.into_iter().sum_finite(), (3.0, 3));
+    }
+
+    #[test]
+    fn sum_finite_empty_and_all_skipped() {
+        assert_eq!(Vec::<f64>::new().into_iter().sum_finite(), (0.0, 0));
+        assert_eq!(vec![f32::NAN].into_iter().sum_finite(), (0.0, 1));
+    }
+
+    #[test]
+    fn mean_finite_skips_non_finite() {
+        let arr = vec![2.0, f64::NAN, 4.0, f64::INFINITY, 6.0];
+        assert_eq!(arr.into_iter().mean_finite(), (Some(4.0), 2));
+    }
+
+    #[test]
+    fn mean_finite_empty_and_all_skipped() {
+        assert_eq!(Vec::<f64>::new().into_iter().mean_finite(), (None, 0));
+        assert_eq!(
+            vec![f64::NAN, f64::INFINITY].into_iter().mean_finite(),
+            (None, 2)
+        );
+    }
+
+    #[test]
+    fn geometric_mean_basic() {
+        let mean = vec![2.0, 8.0].into_iter().geometric_mean().unwrap();
+        assert!((mean - 4.0).abs() < 1e-12);
+        let mean = vec![1u32, 3, 9].into_iter().geometric_mean().unwrap();
+        assert!((mean - 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn geometric_mean_edge_cases() {
+        assert_eq!(Vec::<f64>::new().into_iter().geometric_mean(), None);
+        assert_eq!(vec![5.0, 0.0].into_iter().geometric_mean(), Some(0.0));
+        assert_eq!(vec![5.0, -1.0].into_iter().geometric_mean(), None);
+        assert!(
+            vec![1.0, f64::NAN]
+                .into_iter()
+                .geometric_mean()
+                .unwrap()
+                .is_nan()
+        );
+    }
+
+    #[test]
+    fn geometric_mean_infinite_element() {
+        assert_eq!(
+            vec![1.0, f64::INFINITY].into_iter().geometric_mean(),
+            Some(f64::INFINITY)
+        );
+    }
+
+    #[test]
+    fn geometric_mean_no_overflow() {
+        let mean = std::iter::repeat_n(1e300, 10).geometric_mean().unwrap();
+        assert!((mean / 1e300 - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn harmonic_mean_basic() {
+        let mean = vec![1.0, 2.0, 4.0].into_iter().harmonic_mean().unwrap();
+        assert!((mean - 12.0 / 7.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn harmonic_mean_edge_cases() {
+        assert_eq!(Vec::<f64>::new().into_iter().harmonic_mean(), None);
+        assert_eq!(vec![1.0, 0.0].into_iter().harmonic_mean(), None);
+        assert_eq!(vec![1.0, -3.0].into_iter().harmonic_mean(), None);
+        assert_eq!(vec![7.0].into_iter().harmonic_mean(), Some(7.0));
+    }
+
+    #[test]
+    fn covariance_basic() {
+        let pairs = vec![(1.0, 1.0), (2.0, 3.0), (3.0, 2.0), (4.0, 6.0)];
+        let covariance = pairs.into_iter().covariance().unwrap();
+        assert!((covariance - 1.75).abs() < 1e-12);
+    }
+
+    #[test]
+    fn covariance_empty_and_single() {
+        assert_eq!(Vec::<(f64, f64)>::new().into_iter().covariance(), None);
+        assert_eq!(vec![(1.0, 5.0)].into_iter().covariance(), Some(0.0));
+    }
+
+    #[test]
+    fn covariance_mixed_types() {
+        let xs = vec![1u8, 2, 3];
+        let ys = vec![3.0f32, 2.0, 1.0];
+        let covariance = xs.into_iter().zip(ys).covariance().unwrap();
+        assert!((covariance + 2.0 / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn pearson_correlation_perfect() {
+        let pairs = (0..10).map(|i| (i as f64, 3.0 * i as f64 + 1.0));
+        let r = pairs.pearson_correlation().unwrap();
+        assert!((r - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn pearson_correlation_uncorrelated() {
+        let pairs = vec![(1.0, 1.0), (2.0, 2.0), (3.0, 1.0), (4.0, 2.0), (5.0, 1.5)];
+        let r = pairs.into_iter().pearson_correlation().unwrap();
+        assert!(r.abs() < 0.5);
+    }
+
+    #[test]
+    fn pearson_correlation_undefined() {
+        assert_eq!(
+            Vec::<(f64, f64)>::new().into_iter().pearson_correlation(),
+            None
+        );
+        let constant_y = vec![(1.0, 2.0), (2.0, 2.0), (3.0, 2.0)];
+        assert_eq!(constant_y.into_iter().pearson_correlation(), None);
+    }
+
+    #[test]
+    fn linear_fit_exact_line() {
+        let pairs = (0..20).map(|i| (i as f64, -0.5 * i as f64 + 4.0));
+        let fit = pairs.linear_fit().unwrap();
+        assert!((fit.slope + 0.5).abs() < 1e-12);
+        assert!((fit.intercept - 4.0).abs() < 1e-12);
+        assert!((fit.r_squared - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn linear_fit_noisy() {
+        let pairs = vec![(1.0, 1.0), (2.0, 2.0), (3.0, 1.3), (4.0, 3.75), (5.0, 2.25)];
+        let fit = pairs.into_iter().linear_fit().unwrap();
+        assert!((fit.slope - 0.425).abs() < 1e-12);
+        assert!((fit.intercept - 0.785).abs() < 1e-12);
+        assert!(fit.r_squared > 0.0 && fit.r_squared < 1.0);
+    }
+
+    #[test]
+    fn linear_fit_undefined() {
+        assert_eq!(Vec::<(f64, f64)>::new().into_iter().linear_fit(), None);
+        assert_eq!(vec![(2.0, 1.0), (2.0, 3.0)].into_iter().linear_fit(), None);
+    }
+
+    #[test]
+    fn linear_fit_constant_y() {
+        let fit = vec![(1, 5), (2, 5), (3, 5)]
+            .into_iter()
+            .linear_fit()
+            .unwrap();
+        assert_eq!(fit.slope, 0.0);
+        assert_eq!(fit.intercept, 5.0);
+        assert_eq!(fit.r_squared, 1.0);
+    }
+
+    #[test]
+    fn histogram_basic() {
+        let values = vec![0.0, 0.1, 0.25, 0.5, 0.75, 0.99, 1.0];
+        let histogram = values.into_iter().histogram(2, 0.0, 1.0);
+        assert_eq!(histogram.edges, vec![0.0, 0.5, 1.0]);
+        assert_eq!(histogram.counts, vec![3, 4]);
+        assert_eq!((histogram.below, histogram.above), (0, 0));
+    }
+
+    #[test]
+    fn histogram_out_of_range_and_nan() {
+        let values = vec![-1.0, 2.0, f64::NAN, 0.5];
+        let histogram = values.into_iter().histogram(1, 0.0, 1.0);
+        assert_eq!(histogram.counts, vec![1]);
+        assert_eq!(histogram.below, 1);
+        assert_eq!(histogram.above, 1);
+        assert_eq!(histogram.nan_count, 1);
+    }
+
+    #[test]
+    fn histogram_empty() {
+        let histogram = Vec::<f64>::new().into_iter().histogram(3, 0.0, 3.0);
+        assert_eq!(histogram.counts, vec![0, 0, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "histogram needs at least one bin")]
+    fn histogram_zero_bins() {
+        let _ = vec![1.0].into_iter().histogram(0, 0.0, 1.0);
+    }
+
+    #[test]
+    fn bucketize_by_uneven_edges() {
+        let values = vec![1u32, 5, 10, 50, 100, 500, 1000];
+        let histogram = values
+            .into_iter()
+            .bucketize_by(vec![1.0, 10.0, 100.0, 1000.0]);
+        assert_eq!(histogram.counts, vec![2, 2, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "histogram edges must be strictly increasing")]
+    fn bucketize_by_unsorted_edges() {
+        let _ = vec![1.0].into_iter().bucketize_by(vec![0.0, 2.0, 1.0]);
+    }
+
+    #[test]
+    fn counts_basic() {
+        let arr = vec![1, 2, 1, 3, 1, 2];
+        let counts = arr.iter().counts();
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts[&1], 3);
+        assert_eq!(counts[&2], 2);
+        assert_eq!(counts[&3], 1);
+    }
+
+    #[test]
+    fn counts_empty() {
+        let arr: Vec<i32> = vec![];
+        assert!(arr.iter().counts().is_empty());
+    }
+
+    #[test]
+    fn counts_owned_strings() {
+        let arr = vec!["x".to_string(), "y".to_string(), "x".to_string()];
+        let counts = arr.into_iter().counts();
+        assert_eq!(counts["x"], 2);
+        assert_eq!(counts["y"], 1);
+    }
+
+    #[test]
+    fn counts_by_key_basic() {
+        let arr = vec![1, 11, 2, 22, 1, 33];
+        let counts = arr.iter().counts_by_key(|x| *x % 10);
+        assert_eq!(counts[&1], 3);
+        assert_eq!(counts[&2], 2);
+        assert_eq!(counts[&3], 1);
+    }
+
+    #[test]
+    fn counts_by_key_empty() {
+        let arr: Vec<i32> = vec![];
+        assert!(arr.iter().counts_by_key(|x| *x).is_empty());
+    }
+
+    #[test]
+    fn counts_by_key_categories() {
+        let arr = vec![-2i32, -1, 0, 1, 2, 3];
+        let counts = arr.iter().counts_by_key(|&&x| x.signum());
+        assert_eq!(counts[&-1], 2);
+        assert_eq!(counts[&0], 1);
+        assert_eq!(counts[&1], 3);
+    }
+
+    #[test]
+    fn counts_by_key_empty_categories() {
+        let arr: Vec<i32> = vec![];
+        assert!(arr.iter().counts_by_key(|&&x| x > 0).is_empty());
+    }
+
+    #[test]
+    fn count_partitioned_basic() {
+        let arr = vec!["a", "bb", "ccc", "dd"];
+        assert_eq!(arr.iter().count_partitioned(|s| s.len() == 2), (2, 2));
+    }
+
+    #[test]
+    fn count_partitioned_empty() {
+        let arr: Vec<i32> = vec![];
+        assert_eq!(arr.iter().count_partitioned(|_| true), (0, 0));
+    }
+
+    #[test]
+    fn count_partitioned_none_and_all() {
+        let arr = vec![1, 2, 3];
+        assert_eq!(arr.iter().count_partitioned(|&&x| x > 10), (0, 3));
+        assert_eq!(arr.iter().count_partitioned(|&&x| x > 0), (3, 0));
+    }
+
+    #[test]
+    fn most_common_basic() {
+        let arr = vec![3, 1, 3, 2, 3, 1];
+        assert_eq!(arr.iter().most_common(2), vec![(&3, 3), (&1, 2)]);
+    }
+
+    #[test]
+    fn most_common_ties_by_first_occurrence() {
+        let arr = vec!['c', 'a', 'b', 'a', 'b', 'c'];
+        assert_eq!(
+            arr.into_iter().most_common(3),
+            vec![('c', 2), ('a', 2), ('b', 2)]
+        );
+    }
+
+    #[test]
+    fn most_common_n_larger_than_distinct() {
+        let arr = vec![1, 1, 2];
+        assert_eq!(arr.into_iter().most_common(10), vec![(1, 2), (2, 1)]);
+    }
+
+    #[test]
+    fn most_common_zero_and_empty() {
+        let arr = vec![1, 2, 3];
+        assert!(arr.iter().most_common(0).is_empty());
+
+        let empty: Vec<i32> = vec![];
+        assert!(empty.iter().most_common(3).is_empty());
+    }
+
+    #[test]
+    fn most_common_many_distinct() {
+        let arr = (0..1000).flat_map(|i| std::iter::repeat_n(i, i % 7 + 1));
+        let top = arr.most_common(3);
+        assert_eq!(top, vec![(6, 7), (13, 7), (20, 7)]);
+    }
+
+    #[test]
+    fn mode_basic() {
+        let arr = vec![1, 2, 2, 3, 2, 1];
+        assert_eq!(arr.iter().mode(), Some(&2));
+    }
+
+    #[test]
+    fn mode_tie_prefers_first() {
+        let arr = vec!["b", "a", "a", "b"];
+        assert_eq!(arr.into_iter().mode(), Some("b"));
+    }
+
+    #[test]
+    fn mode_empty() {
+        let arr: Vec<i32> = vec![];
+        assert_eq!(arr.iter().mode(), None);
+    }
+
+    #[test]
+    fn modes_multiple() {
+        let arr = vec![3, 1, 2, 1, 3, 2, 4];
+        assert_eq!(arr.into_iter().modes(), vec![3, 1, 2]);
+    }
+
+    #[test]
+    fn modes_single_and_empty() {
+        let arr = vec![1, 2, 2];
+        assert_eq!(arr.into_iter().modes(), vec![2]);
+
+        let empty: Vec<i32> = vec![];
+        assert!(empty.into_iter().modes().is_empty());
+    }
+
+    #[test]
+    fn entropy_uniform() {
+        let arr = vec![1, 2, 3, 4, 5, 6, 7, 8];
+        assert!((arr.iter().entropy() - 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn entropy_skewed() {
+        let arr = vec!['a', 'a', 'a', 'b'];
+        let expected = -(0.75f64 * 0.75f64.log2() + 0.25 * 0.25f64.log2());
+        assert!((arr.iter().entropy() - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn entropy_empty_and_constant() {
+        let empty: Vec<i32> = vec![];
+        assert_eq!(empty.iter().entropy(), 0.0);
+        assert_eq!(vec![5; 10].into_iter().entropy(), 0.0);
+    }
+
+    #[test]
+    fn entropy_by_key_basic() {
+        let words = vec!["apple", "avocado", "banana", "blueberry"];
+        let entropy = words.iter().entropy_by_key(|s| s.chars().next());
+        assert!((entropy - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn entropy_by_key_empty() {
+        let empty: Vec<&str> = vec![];
+        assert_eq!(empty.iter().entropy_by_key(|s| s.len()), 0.0);
+    }
+
+    #[test]
+    fn rolling_mean_empty_warm_up() {
+        let arr = vec![2.0, 4.0, 6.0, 8.0, 10.0];
+        let means = arr
+            .into_iter()
+            .rolling_mean(3, WarmUp::Empty)
+            .collect::<Vec<_>>();
+        assert_eq!(means, vec![None, None, Some(4.0), Some(6.0), Some(8.0)]);
+    }
+
+    #[test]
+    fn rolling_mean_partial_warm_up() {
+        let arr = vec![2u8, 4, 6];
+        let means = arr
+            .into_iter()
+            .rolling_mean(5, WarmUp::Partial)
+            .collect::<Vec<_>>();
+        assert_eq!(means, vec![Some(2.0), Some(3.0), Some(4.0)]);
+    }
+
+    #[test]
+    fn rolling_mean_window_of_one() {
+        let arr = vec![1.5, -2.5];
+        let means = arr
+            .into_iter()
+            .rolling_mean(1, WarmUp::Empty)
+            .collect::<Vec<_>>();
+        assert_eq!(means, vec![Some(1.5), Some(-2.5)]);
+    }
+
+    #[test]
+    fn rolling_mean_nan_leaves_window() {
+        let arr = vec![1.0, f64::NAN, 3.0, 5.0, 7.0];
+        let means = arr
+            .into_iter()
+            .rolling_mean(2, WarmUp::Empty)
+            .collect::<Vec<_>>();
+        assert_eq!(means[0], None);
+        assert!(means[1].unwrap().is_nan());
+        assert!(means[2].unwrap().is_nan());
+        assert_eq!(&means[3..], &[Some(4.0), Some(6.0)]);
+    }
+
+    #[test]
+    fn rolling_mean_infinity_leaves_window() {
+        let arr = vec![f64::INFINITY, 1.0, 3.0, f64::NEG_INFINITY, f64::INFINITY];
+        let means = arr
+            .into_iter()
+            .rolling_mean(2, WarmUp::Partial)
+            .collect::<Vec<_>>();
+        assert_eq!(means[0], Some(f64::INFINITY));
+        assert_eq!(means[1], Some(f64::INFINITY));
+        assert_eq!(means[2], Some(2.0));
+        assert_eq!(means[3], Some(f64::NEG_INFINITY));
+        assert!(means[4].unwrap().is_nan());
+    }
+
+    #[test]
+    fn rolling_mean_empty() {
+        let arr: Vec<f64> = vec![];
+        assert_eq!(arr.into_iter().rolling_mean(3, WarmUp::Empty).next(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "window size must be non-zero")]
+    fn rolling_mean_zero_window() {
+        let _ = vec![1.0].into_iter().rolling_mean(0, WarmUp::Empty);
+    }
+
+    #[test]
+    fn ewma_basic() {
+        let arr = vec![4.0, 8.0, 0.0];
+        let averages = arr.into_iter().ewma(0.25).collect::<Vec<_>>();
+        assert_eq!(averages, vec![4.0, 5.0, 3.75]);
+    }
+
+    #[test]
+    fn ewma_alpha_one_tracks_input() {
+        let arr = vec![1u16, 5, 3];
+        let averages = arr.into_iter().ewma(1.0).collect::<Vec<_>>();
+        assert_eq!(averages, vec![1.0, 5.0, 3.0]);
+    }
+
+    #[test]
+    fn ewma_empty() {
+        let arr: Vec<f64> = vec![];
+        assert_eq!(arr.into_iter().ewma(0.5).next(), None);
+    }
+
+    #[test]
+    fn ewma_converges_to_constant() {
+        let last = std::iter::once(0.0)
+            .chain(std::iter::repeat_n(10.0, 200))
+            .ewma(0.1)
+            .last()
+            .unwrap();
+        assert!((last - 10.0).abs() < 1e-6);
+    }
+
+    #[test]
+    #[should_panic(expected = "smoothing factor must be within (0, 1]")]
+    fn ewma_invalid_alpha() {
+        let _ = vec![1.0].into_iter().ewma(0.0);
+    }
+
+    #[test]
+    fn rolling_min_basic() {
+        let arr = vec![3, 1, 4, 1, 5, 9, 2, 6];
+        let minima = arr.iter().rolling_min(3).collect::<Vec<_>>();
+        assert_eq!(minima, vec![&3, &1, &1, &1, &1, &1, &2, &2]);
+    }
+
+    #[test]
+    fn rolling_max_basic() {
+        let arr = vec![3, 1, 4, 1, 5, 9, 2, 6];
+        let maxima = arr.iter().rolling_max(3).collect::<Vec<_>>();
+        assert_eq!(maxima, vec![&3, &3, &4, &4, &5, &9, &9, &9]);
+    }
+
+    #[test]
+    fn rolling_min_window_of_one() {
+        let arr = vec![2.5, 1.5, 3.5];
+        let minima = arr.into_iter().rolling_min(1).collect::<Vec<_>>();
+        assert_eq!(minima, vec![2.5, 1.5, 3.5]);
+    }
+
+    #[test]
+    fn rolling_max_matches_naive() {
+        let arr = (0..500).map(|i| (i * 7919) % 101).collect::<Vec<_>>();
+        let maxima = arr.iter().copied().rolling_max(10).collect::<Vec<_>>();
+        let naive = (0..arr.len())
+            .map(|i| *arr[i.saturating_sub(9)..=i].iter().max().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(maxima, naive);
+    }
+
+    #[test]
+    fn rolling_min_empty() {
+        let arr: Vec<f64> = vec![];
+        assert_eq!(arr.into_iter().rolling_min(3).next(), None);
+    }
+
+    #[test]
+    fn rolling_min_with_nan_expires() {
+        let arr = vec![f64::NAN, 2.0, 1.0, 3.0];
+        let minima = arr.into_iter().rolling_min(2).collect::<Vec<_>>();
+        assert!(minima[0].is_nan());
+        assert_eq!(&minima[2..], &[1.0, 1.0]);
+    }
+
+    #[test]
+    fn rolling_min_nan_does_not_block_later_minimum() {
+        let minima = vec![5.0, f64::NAN, 1.0]
+            .into_iter()
+            .rolling_min(3)
+            .collect::<Vec<_>>();
+        assert_eq!(minima, vec![5.0, 5.0, 1.0]);
+    }
+
+    #[test]
+    fn rolling_max_nan_does_not_block_later_maximum() {
+        let maxima = vec![1.0, f64::NAN, 5.0, 2.0, f64::NAN]
+            .into_iter()
+            .rolling_max(2)
+            .collect::<Vec<_>>();
+        assert_eq!(maxima, vec![1.0, 1.0, 5.0, 5.0, 2.0]);
+    }
+
+    #[test]
+    fn rolling_extreme_all_nan_window_yields_latest() {
+        let minima = vec![1.0, f64::NAN, f64::NAN]
+            .into_iter()
+            .rolling_min(2)
+            .collect::<Vec<_>>();
+        assert_eq!(minima[..2], [1.0, 1.0]);
+        assert!(minima[2].is_nan());
+    }
+
+    #[test]
+    fn rolling_min_by_partial_key_matches_naive() {
+        let arr = (0..300)
+            .map(|i| (i, ((i * 7919) % 97) as f64))
+            .collect::<Vec<_>>();
+        let minima = arr
+            .iter()
+            .rolling_min_by_partial_key(7, |(_, k)| *k)
+            .map(|(i, _)| *i)
+            .collect::<Vec<_>>();
+        let naive = (0..arr.len())
+            .map(|i| {
+                arr[i.saturating_sub(6)..=i]
+                    .iter()
+                    .min_by_partial_key(|(_, k)| *k)
+                    .unwrap()
+                    .0
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(minima, naive);
+    }
+
+    #[test]
+    fn rolling_max_by_partial_key_skips_nan_keys() {
+        let arr = [('a', 1.0), ('b', f64::NAN), ('c', 0.5), ('d', f64::NAN)];
+        let maxima = arr
+            .iter()
+            .rolling_max_by_partial_key(2, |(_, k)| *k)
+            .map(|(c, _)| *c)
+            .collect::<String>();
+        assert_eq!(maxima, "aacc");
+    }
+
+    #[test]
+    #[should_panic(expected = "window size must be non-zero")]
+    fn rolling_max_zero_window() {
+        let _ = vec![1].into_iter().rolling_max(0);
+    }
+
+    #[test]
+    fn zscore_basic() {
+        let arr = vec![5.0, 10.0, 15.0];
+        let scores = arr.into_iter().zscore(10.0, 5.0).collect::<Vec<_>>();
+        assert_eq!(scores, vec![-1.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn zscore_zero_stddev() {
+        let arr = vec![3, 3, 3];
+        let scores = arr.into_iter().zscore(3.0, 0.0).collect::<Vec<_>>();
+        assert_eq!(scores, vec![0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn standardize_has_zero_mean_unit_variance() {
+        let arr = vec![1.0, 4.0, 2.0, 8.0, 5.0];
+        let scores = arr.into_iter().standardize().collect::<Vec<_>>();
+        let summary = scores.into_iter().describe().unwrap();
+        assert!(summary.mean.abs() < 1e-12);
+        assert!((summary.variance - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn standardize_empty_and_constant() {
+        let empty: Vec<f64> = vec![];
+        assert_eq!(empty.into_iter().standardize().next(), None);
+
+        let constant = vec![7.0; 3];
+        let scores = constant.into_iter().standardize().collect::<Vec<_>>();
+        assert_eq!(scores, vec![0.0; 3]);
+    }
+
+    #[test]
+    fn standardize_with_nan() {
+        let arr = vec![1.0, f64::NAN, 3.0];
+        let scores = arr.into_iter().standardize().collect::<Vec<_>>();
+        assert_eq!(scores[0], -1.0);
+        assert!(scores[1].is_nan());
+        assert_eq!(scores[2], 1.0);
+    }
+
+    #[test]
+    fn standardize_constant_with_nan() {
+        let scores = vec![1.0, f64::NAN, 1.0]
+            .into_iter()
+            .standardize()
+            .collect::<Vec<_>>();
+        assert_eq!(scores[0], 0.0);
+        assert!(scores[1].is_nan());
+        assert_eq!(scores[2], 0.0);
+    }
+
+    #[test]
+    fn normalize_min_max_basic() {
+        let arr = vec![2.0, 3.0, 6.0];
+        let scaled = arr
+            .into_iter()
+            .normalize_min_max(2.0, 6.0)
+            .collect::<Vec<_>>();
+        assert_eq!(scaled, vec![0.0, 0.25, 1.0]);
+    }
+
+    #[test]
+    fn normalize_min_max_out_of_range() {
+        let arr = vec![-5.0, 15.0];
+        let scaled = arr
+            .into_iter()
+            .normalize_min_max(0.0, 10.0)
+            .collect::<Vec<_>>();
+        assert_eq!(scaled, vec![-0.5, 1.5]);
+    }
+
+    #[test]
+    fn normalize_min_max_constant_range() {
+        let arr = vec![4.0, 4.0];
+        let scaled = arr
+            .into_iter()
+            .normalize_min_max(4.0, 4.0)
+            .collect::<Vec<_>>();
+        assert_eq!(scaled, vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn normalize_basic() {
+        let arr = vec![3u8, 1, 5];
+        let scaled = arr.into_iter().normalize().collect::<Vec<_>>();
+        assert_eq!(scaled, vec![0.5, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn normalize_constant_and_empty() {
+        let constant = vec![9.0; 4];
+        let scaled = constant.into_iter().normalize().collect::<Vec<_>>();
+        assert_eq!(scaled, vec![0.0; 4]);
+
+        let empty: Vec<f64> = vec![];
+        assert_eq!(empty.into_iter().normalize().next(), None);
+    }
+
+    #[test]
+    fn normalize_with_nan() {
+        let arr = vec![0.0, f64::NAN, 2.0];
+        let scaled = arr.into_iter().normalize().collect::<Vec<_>>();
+        assert_eq!(scaled[0], 0.0);
+        assert!(scaled[1].is_nan());
+        assert_eq!(scaled[2], 1.0);
+    }
+
+    #[test]
+    fn normalize_constant_with_nan() {
+        let scaled = vec![1.0, f64::NAN, 1.0]
+            .into_iter()
+            .normalize()
+            .collect::<Vec<_>>();
+        assert_eq!(scaled[0], 0.0);
+        assert!(scaled[1].is_nan());
+        assert_eq!(scaled[2], 0.0);
+    }
+
+    #[test]
+    fn clamp_values_basic() {
+        let arr = vec![-5, 0, 5, 10, 15];
+        let clamped = arr
+            .into_iter()
+            .clamp_values(0, 10, NanPolicy::PassThrough)
+            .collect::<Vec<_>>();
+        assert_eq!(clamped, vec![0, 0, 5, 10, 10]);
+    }
+
+    #[test]
+    fn clamp_values_nan_pass_through() {
+        let arr = vec![2.0, f64::NAN];
+        let clamped = arr
+            .into_iter()
+            .clamp_values(0.0, 1.0, NanPolicy::PassThrough)
+            .collect::<Vec<_>>();
+        assert_eq!(clamped[0], 1.0);
+        assert!(clamped[1].is_nan());
+    }
+
+    #[test]
+    fn clamp_values_nan_replace() {
+        let arr = vec![f64::NAN, 0.5, f64::NAN];
+        let clamped = arr
+            .into_iter()
+            .clamp_values(0.0, 1.0, NanPolicy::Replace(0.0))
+            .collect::<Vec<_>>();
+        assert_eq!(clamped, vec![0.0, 0.5, 0.0]);
+    }
+
+    #[test]
+    fn clamp_values_nan_drop() {
+        let arr = vec![f64::NAN, f64::NAN, -1.0, f64::NAN];
+        let clamped = arr
+            .into_iter()
+            .clamp_values(0.0, 1.0, NanPolicy::Drop)
+            .collect::<Vec<_>>();
+        assert_eq!(clamped, vec![0.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "clamp bounds must satisfy lo <= hi")]
+    fn clamp_values_invalid_bounds() {
+        let _ = vec![1.0]
+            .into_iter()
+            .clamp_values(2.0, 1.0, NanPolicy::Drop);
+    }
+
+    #[test]
+    fn nan_to_value() {
+        let arr = vec![f64::NAN, 2.0, f64::INFINITY];
+        let replaced = arr
+            .into_iter()
+            .nan_to(Fill::Value(-1.0))
+            .collect::<Vec<_>>();
+        assert_eq!(replaced, vec![-1.0, 2.0, f64::INFINITY]);
+    }
+
+    #[test]
+    fn nan_to_previous_leading_nan() {
+        let arr = vec![f64::NAN, 5.0, f64::NAN, f64::NAN, 6.0];
+        let replaced = arr.into_iter().nan_to(Fill::Previous).collect::<Vec<_>>();
+        assert!(replaced[0].is_nan());
+        assert_eq!(&replaced[1..], &[5.0, 5.0, 5.0, 6.0]);
+    }
+
+    #[test]
+    fn nan_to_f32() {
+        let arr = vec![1.5f32, f32::NAN];
+        let replaced = arr.into_iter().nan_to(Fill::Value(0.0)).collect::<Vec<_>>();
+        assert_eq!(replaced, vec![1.5, 0.0]);
+    }
+
+    #[test]
+    fn replace_non_finite_value() {
+        let arr = vec![f64::NEG_INFINITY, 1.0, f64::NAN, f64::INFINITY];
+        let replaced = arr
+            .into_iter()
+            .replace_non_finite(Fill::Value(0.0))
+            .collect::<Vec<_>>();
+        assert_eq!(replaced, vec![0.0, 1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn replace_non_finite_previous() {
+        let arr = vec![3.0, f64::INFINITY, 4.0, f64::NEG_INFINITY];
+        let replaced = arr
+            .into_iter()
+            .replace_non_finite(Fill::Previous)
+            .collect::<Vec<_>>();
+        assert_eq!(replaced, vec![3.0, 3.0, 4.0, 4.0]);
+    }
+
+    #[test]
+    fn filter_finite_f64() {
+        let arr = vec![f64::INFINITY, 1.5, f64::NAN, -2.5];
+        let finite = arr.into_iter().filter_finite().collect::<Vec<_>>();
+        assert_eq!(finite, vec![1.5, -2.5]);
+    }
+
+    #[test]
+    fn filter_finite_f32_references() {
+        let arr = vec![f32::NAN, 3.0f32, f32::NEG_INFINITY];
+        let finite = arr.iter().filter_finite().collect::<Vec<_>>();
+        assert_eq!(finite, vec![&3.0]);
+    }
+
+    #[test]
+    fn filter_finite_all_non_finite() {
+        let arr = vec![f64::NAN, f64::INFINITY];
+        assert_eq!(arr.iter().filter_finite().next(), None);
+    }
+
+    #[test]
+    fn filter_finite_composes_with_partial_key() {
+        let arr = vec![f64::NAN, 3.0, f64::INFINITY, 1.0];
+        let max = arr.iter().filter_finite().max_by_partial_key(|&&x| x);
+        assert_eq!(max, Some(&3.0));
+    }
+
+    #[test]
+    fn min_by_float_key_basic() {
+        let arr = vec![3.2, 1.5, 2.8, 0.9];
+        assert_eq!(arr.iter().min_by_float_key(|&&x| x), Some(&0.9));
+    }
+
+    #[test]
+    fn min_by_float_key_empty() {
+        let arr: Vec<f64> = vec![];
+        assert_eq!(arr.iter().min_by_float_key(|&&x| x), None);
+    }
+
+    #[test]
+    fn min_by_float_key_nan_is_deterministic() {
+        let arr = vec![f64::NAN, 1.0, f64::NAN];
+        assert_eq!(arr.iter().min_by_float_key(|&&x| x), Some(&1.0));
+
+        let arr = vec![1.0, -f64::NAN];
+        assert!(arr.iter().min_by_float_key(|&&x| x).unwrap().is_nan());
+    }
+
+    #[test]
+    fn max_by_float_key_basic() {
+        let people = vec![("Alice", 1.62), ("Bob", 1.85), ("Charlie", 1.71)];
+        let tallest = people.iter().max_by_float_key(|(_, h)| *h);
+        assert_eq!(tallest, Some(&("Bob", 1.85)));
+    }
+
+    #[test]
+    fn max_by_float_key_nan_is_greatest() {
+        let arr = vec![f64::INFINITY, f64::NAN, 1.0];
+        assert!(arr.iter().max_by_float_key(|&&x| x).unwrap().is_nan());
+    }
+
+    #[test]
+    fn sorted_by_float_key_basic() {
+        let arr = vec![2.5, -1.0, f64::INFINITY, 0.0];
+        let sorted = arr
+            .into_iter()
+            .sorted_by_float_key(|&x| x)
+            .collect::<Vec<_>>();
+        assert_eq!(sorted, vec![-1.0, 0.0, 2.5, f64::INFINITY]);
+    }
+
+    #[test]
+    fn sorted_by_float_key_stable() {
+        let arr = vec![(1.0, 'a'), (0.0, 'b'), (1.0, 'c')];
+        let sorted = arr
+            .into_iter()
+            .sorted_by_float_key(|(x, _)| *x)
+            .collect::<Vec<_>>();
+        assert_eq!(sorted, vec![(0.0, 'b'), (1.0, 'a'), (1.0, 'c')]);
+    }
+
+    #[test]
+    fn sorted_by_float_key_nan_positions() {
+        let arr = vec![f64::NAN, 1.0, -f64::NAN, 0.0];
+        let sorted = arr
+            .into_iter()
+            .sorted_by_float_key(|&x| x)
+            .collect::<Vec<_>>();
+        assert!(sorted[0].is_nan() && sorted[0].is_sign_negative());
+        assert_eq!(&sorted[1..3], &[0.0, 1.0]);
+        assert!(sorted[3].is_nan() && sorted[3].is_sign_positive());
+    }
+
+    #[test]
+    fn ordered_partial_ordering() {
+        assert!(OrderedPartial(1.0) < OrderedPartial(2.0));
+        assert_eq!(OrderedPartial(2.0), OrderedPartial(2.0));
+        assert_eq!(
+            OrderedPartial(f64::NAN).cmp(&OrderedPartial(1.0)),
+            std::cmp::Ordering::Equal
+        );
+        assert_eq!(OrderedPartial(f64::NAN), OrderedPartial(f64::NAN));
+    }
+
+    #[test]
+    fn ordered_partial_binary_heap() {
+        let mut heap = vec![0.3, 0.9, 0.1]
+            .into_iter()
+            .map(OrderedPartial)
+            .collect::<std::collections::BinaryHeap<_>>();
+        let drained =
+            std::iter::from_fn(|| heap.pop().map(OrderedPartial::into_inner)).collect::<Vec<_>>();
+        assert_eq!(drained, vec![0.9, 0.3, 0.1]);
+    }
+
+    #[test]
+    fn ordered_partial_btree_map() {
+        let mut map = std::collections::BTreeMap::new();
+        map.insert(OrderedPartial(2.5), "b");
+        map.insert(OrderedPartial(-1.0), "a");
+        let values = map.into_values().collect::<Vec<_>>();
+        assert_eq!(values, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn ordered_partial_with_std_min_by_key() {
+        let arr = vec![(1, 3.5), (2, 0.5), (3, 2.5)];
+        let min = arr.iter().min_by_key(|(_, x)| OrderedPartial(*x));
+        assert_eq!(min, Some(&(2, 0.5)));
+    }
+
+    #[test]
+    fn min_by_partial_key_with_basic() {
+        let arr = vec![3.2, 1.5, 2.8, 0.9];
+        let min = arr.iter().min_by_partial_key_with(|&&x| x * 10.0);
+        assert_eq!(min, Some((&0.9, 9.0)));
+    }
+
+    #[test]
+    fn min_by_partial_key_with_empty() {
+        let arr: Vec<f64> = vec![];
+        assert_eq!(arr.iter().min_by_partial_key_with(|&&x| x), None);
+    }
+
+    #[test]
+    fn min_by_partial_key_with_calls_key_once() {
+        let arr = vec![5, 3, 8, 1, 9];
+        let mut calls = 0;
+        let min = arr.iter().min_by_partial_key_with(|&&x| {
+            calls += 1;
+            x
+        });
+        assert_eq!(min, Some((&1, 1)));
+        assert_eq!(calls, 5);
+    }
+
+    #[test]
+    fn min_max_by_partial_key_with_ties() {
+        let arr = vec![(1, 'a'), (0, 'b'), (0, 'c'), (1, 'd')];
+        let min = arr.iter().min_by_partial_key_with(|(x, _)| *x);
+        let max = arr.iter().max_by_partial_key_with(|(x, _)| *x);
+        assert_eq!(min, Some((&(0, 'b'), 0)));
+        assert_eq!(max, Some((&(1, 'd'), 1)));
+    }
+
+    #[test]
+    fn max_by_partial_key_with_skips_nan() {
+        let arr = vec![1.0, f64::NAN, 2.0, 0.5];
+        let max = arr.iter().max_by_partial_key_with(|&&x| x);
+        assert_eq!(max, Some((&2.0, 2.0)));
+    }
+
+    #[test]
+    fn k_smallest_sorted_by_partial_key_with_keys() {
+        let arr = vec![4.0, 1.0, 3.0, 2.0];
+        let smallest = arr
+            .into_iter()
+            .k_smallest_sorted_by_partial_key_with(2, |&x| x * x);
+        assert_eq!(smallest, vec![(1.0, 1.0), (2.0, 4.0)]);
+    }
+
+    #[test]
+    fn positions_of_min_by_partial_key_ties() {
+        let arr = vec![3, 1, 4, 1, 5, 1];
+        assert_eq!(
+            arr.iter().positions_of_min_by_partial_key(|&&x| x),
+            vec![1, 3, 5]
+        );
+    }
+
+    #[test]
+    fn positions_of_max_by_partial_key_ties() {
+        let arr = vec![5, 1, 5, 2];
+        assert_eq!(
+            arr.iter().positions_of_max_by_partial_key(|&&x| x),
+            vec![0, 2]
+        );
+    }
+
+    #[test]
+    fn positions_of_extremes_empty() {
+        let arr: Vec<f64> = vec![];
+        assert!(
+            arr.iter()
+                .positions_of_min_by_partial_key(|&&x| x)
+                .is_empty()
+        );
+        assert!(
+            arr.iter()
+                .positions_of_max_by_partial_key(|&&x| x)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn positions_of_extremes_skip_nan() {
+        let arr = vec![2.0, f64::NAN, 0.5, 0.5, f64::NAN];
+        assert_eq!(
+            arr.iter().positions_of_min_by_partial_key(|&&x| x),
+            vec![2, 3]
+        );
+        assert_eq!(arr.iter().positions_of_max_by_partial_key(|&&x| x), vec![0]);
+    }
+
+    #[test]
+    fn minmax_with_indices_by_partial_key_basic() {
+        let arr = vec![4, 2, 8, 2, 8, 5];
+        let result = arr.iter().minmax_with_indices_by_partial_key(|&&x| x);
+        assert_eq!(result, Some(((1, &2), (4, &8))));
+    }
+
+    #[test]
+    fn minmax_with_indices_by_partial_key_single_and_empty() {
+        let one = vec![7];
+        assert_eq!(
+            one.iter().minmax_with_indices_by_partial_key(|&&x| x),
+            Some(((0, &7), (0, &7)))
+        );
+        let none: Vec<i32> = vec![];
+        assert_eq!(
+            none.iter().minmax_with_indices_by_partial_key(|&&x| x),
+            None
+        );
+    }
+
+    #[test]
+    fn minmax_with_indices_by_partial_key_by_field() {
+        let points = vec![(1.0, 3.0), (-2.0, 0.5), (4.0, -1.0)];
+        let (min, max) = points
+            .iter()
+            .minmax_with_indices_by_partial_key(|p| p.1)
+            .unwrap();
+        assert_eq!(min, (2, &(4.0, -1.0)));
+        assert_eq!(max, (0, &(1.0, 3.0)));
+    }
+
+    #[test]
+    fn argsort_by_partial_key_basic() {
+        let arr = vec![3, 1, 2];
+        assert_eq!(arr.iter().argsort_by_partial_key(|&&x| x), vec![1, 2, 0]);
+    }
+
+    #[test]
+    fn argsort_by_partial_key_stable_with_nan() {
+        let arr = vec![2.0, 1.0, 2.0, 0.0];
+        assert_eq!(arr.iter().argsort_by_partial_key(|&&x| x), vec![3, 1, 0, 2]);
+
+        let with_nan = vec![1.0, f64::NAN, 0.5];
+        let order = with_nan.iter().argsort_by_partial_key(|&&x| x);
+        assert_eq!(order, vec![2, 0, 1]);
+    }
+
+    #[test]
+    fn argsort_by_partial_key_empty() {
+        let arr: Vec<f64> = vec![];
+        assert!(arr.into_iter().argsort_by_partial_key(|&x| x).is_empty());
+    }
+
+    #[test]
+    fn cumsum_basic() {
+        let arr = vec![1, 2, 3, 4];
+        assert_eq!(
+            arr.into_iter().cumsum().collect::<Vec<_>>(),
+            vec![1, 3, 6, 10]
+        );
+    }
+
+    #[test]
+    fn cumsum_empty_and_size_hint() {
+        let empty: Vec<f64> = vec![];
+        assert_eq!(empty.into_iter().cumsum().next(), None);
+        let arr = vec![1.5, 2.5];
+        assert_eq!(arr.into_iter().cumsum().size_hint(), (2, Some(2)));
+    }
+
+    #[test]
+    fn cumsum_checked_overflow_poisons_rest() {
+        let arr = vec![i32::MAX - 1, 1, 1, -5];
+        let totals: Vec<_> = arr.into_iter().cumsum_checked().collect();
+        assert_eq!(totals, vec![Some(i32::MAX - 1), Some(i32::MAX), None, None]);
+    }
+
+    #[test]
+    fn cumsum_wrapping_and_saturating() {
+        let arr = vec![250u8, 10, 1];
+        let wrapping: Vec<_> = arr.iter().copied().cumsum_wrapping().collect();
+        let saturating: Vec<_> = arr.iter().copied().cumsum_saturating().collect();
+        assert_eq!(wrapping, vec![250, 4, 5]);
+        assert_eq!(saturating, vec![250, 255, 255]);
+    }
+
+    #[test]
+    fn cummax_by_partial_key_basic() {
+        let arr = vec![1, 3, 2, 5, 4];
+        let result: Vec<_> = arr.into_iter().cummax_by_partial_key(|&x| x).collect();
+        assert_eq!(result, vec![1, 3, 3, 5, 5]);
+    }
+
+    #[test]
+    fn cummin_by_partial_key_basic() {
+        let arr = vec![4.0, 2.0, 3.0, 1.0];
+        let result: Vec<_> = arr.into_iter().cummin_by_partial_key(|&x| x).collect();
+        assert_eq!(result, vec![4.0, 2.0, 2.0, 1.0]);
+    }
+
+    #[test]
+    fn cummax_by_partial_key_leading_nan() {
+        let arr = vec![f64::NAN, 1.0, 0.5, 2.0];
+        let result: Vec<_> = arr.into_iter().cummax_by_partial_key(|&x| x).collect();
+        assert!(result[0].is_nan());
+        assert_eq!(&result[1..], &[1.0, 1.0, 2.0]);
+    }
+
+    #[test]
+    fn cummin_by_partial_key_keeps_first_tie() {
+        let arr = vec![(2, 'a'), (1, 'b'), (1, 'c')];
+        let result: Vec<_> = arr.into_iter().cummin_by_partial_key(|p| p.0).collect();
+        assert_eq!(result, vec![(2, 'a'), (1, 'b'), (1, 'b')]);
+    }
+
+    #[test]
+    fn diffs_basic() {
+        let arr = vec![1, 4, 9, 16];
+        assert_eq!(arr.into_iter().diffs().collect::<Vec<_>>(), vec![3, 5, 7]);
+    }
+
+    #[test]
+    fn diffs_short_inputs() {
+        let empty: Vec<i32> = vec![];
+        assert_eq!(empty.into_iter().diffs().next(), None);
+        let single = vec![5];
+        assert_eq!(single.into_iter().diffs().next(), None);
+    }
+
+    #[test]
+    fn diffs_size_hint() {
+        let arr = vec![1.0, 2.0, 4.0];
+        let mut iter = arr.into_iter().diffs();
+        assert_eq!(iter.size_hint(), (2, Some(2)));
+        iter.next();
+        assert_eq!(iter.size_hint(), (1, Some(1)));
+    }
+
+    #[test]
+    fn diffs_from_includes_first() {
+        let arr = vec![3, 3, 1];
+        let result: Vec<_> = arr.into_iter().diffs_from(1).collect();
+        assert_eq!(result, vec![2, 0, -2]);
+    }
+
+    #[test]
+    fn delta_encode_signed() {
+        let arr = vec![10i32, 7, 7, 12];
+        let encoded: Vec<_> = arr.into_iter().delta_encode().collect();
+        assert_eq!(encoded, vec![10, -3, 0, 5]);
+    }
+
+    #[test]
+    fn delta_encode_decode_round_trip_extremes() {
+        let arr = vec![0u8, 255, 0, 128, 127];
+        let encoded: Vec<i8> = arr.iter().copied().delta_encode().collect();
+        let decoded: Vec<u8> = encoded.into_iter().delta_decode().collect();
+        assert_eq!(decoded, arr);
+
+        let signed = vec![i64::MIN, i64::MAX, -1];
+        let encoded: Vec<i64> = signed.iter().copied().delta_encode().collect();
+        let decoded: Vec<i64> = encoded.into_iter().delta_decode().collect();
+        assert_eq!(decoded, signed);
+    }
+
+    #[test]
+    fn delta_encode_decode_empty() {
+        let empty: Vec<usize> = vec![];
+        assert_eq!(empty.into_iter().delta_encode().next(), None);
+        let none: Vec<isize> = vec![];
+        assert_eq!(none.into_iter().delta_decode::<usize>().next(), None);
+    }
+
+    #[test]
+    fn derivative_irregular_spacing() {
+        let points = vec![(0, 1.0), (2, 5.0), (3, 4.0), (7, 4.0)];
+        let slopes: Vec<f64> = points.into_iter().derivative().collect();
+        assert_eq!(slopes, vec![2.0, -1.0, 0.0]);
+    }
+
+    #[test]
+    fn derivative_short_inputs() {
+        let empty: Vec<(f64, f64)> = vec![];
+        assert_eq!(empty.into_iter().derivative().next(), None);
+        let single = vec![(1.0, 2.0)];
+        assert_eq!(single.into_iter().derivative().size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn derivative_repeated_x() {
+        let points = vec![(1.0, 0.0), (1.0, 1.0)];
+        let slopes: Vec<f64> = points.into_iter().derivative().collect();
+        assert_eq!(slopes, vec![f64::INFINITY]);
+    }
+
+    #[test]
+    fn integrate_trapezoid_linear() {
+        let points = (0..=10).map(|i| (i as f64 / 10.0, 2.0 * i as f64 / 10.0));
+        assert!((points.integrate_trapezoid() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn integrate_trapezoid_short_inputs() {
+        let empty: Vec<(f64, f64)> = vec![];
+        assert_eq!(empty.into_iter().integrate_trapezoid(), 0.0);
+        let single = vec![(1.0, 5.0)];
+        assert_eq!(single.into_iter().integrate_trapezoid(), 0.0);
+    }
+
+    #[test]
+    fn cumulative_trapezoid_matches_total() {
+        let points = vec![(0.0, 1.0), (0.5, 2.0), (2.0, 0.0), (3.0, -1.0)];
+        let running: Vec<f64> = points.iter().copied().cumulative_trapezoid().collect();
+        assert_eq!(running, vec![0.0, 0.75, 2.25, 1.75]);
+        assert_eq!(points.into_iter().integrate_trapezoid(), 1.75);
+    }
+
+    #[test]
+    fn cumulative_trapezoid_infinite_sample() {
+        let points = vec![(0.0, 1.0), (1.0, f64::INFINITY), (2.0, 1.0)];
+        let running: Vec<f64> = points.iter().copied().cumulative_trapezoid().collect();
+        assert_eq!(running, vec![0.0, f64::INFINITY, f64::INFINITY]);
+        assert_eq!(points.into_iter().integrate_trapezoid(), f64::INFINITY);
+    }
+
+    #[test]
+    fn downsample_aggregates() {
+        let arr = vec![5, 1, 3, 2, 9, 4];
+        let run = |agg| arr.iter().copied().downsample(3, agg).collect::<Vec<_>>();
+        assert_eq!(run(Aggregate::Mean), vec![3.0, 5.0]);
+        assert_eq!(run(Aggregate::Min), vec![1.0, 2.0]);
+        assert_eq!(run(Aggregate::Max), vec![5.0, 9.0]);
+        assert_eq!(run(Aggregate::First), vec![5.0, 2.0]);
+        assert_eq!(run(Aggregate::Last), vec![3.0, 4.0]);
+    }
+
+    #[test]
+    fn downsample_partial_group_and_size_hint() {
+        let arr = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        let iter = arr.into_iter().downsample(2, Aggregate::Mean);
+        assert_eq!(iter.size_hint(), (3, Some(3)));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![1.5, 3.5, 5.0]);
+    }
+
+    #[test]
+    fn downsample_min_max_skip_nan() {
+        let arr = vec![f64::NAN, 2.0, f64::NAN, f64::NAN];
+        let mins: Vec<_> = arr.into_iter().downsample(2, Aggregate::Min).collect();
+        assert_eq!(mins[0], 2.0);
+        assert!(mins[1].is_nan());
+    }
+
+    #[test]
+    fn downsample_with_custom() {
+        let words = vec!["a", "bb", "ccc", "dddd"];
+        let joined: Vec<String> = words
+            .into_iter()
+            .downsample_with(3, |group| group.concat())
+            .collect();
+        assert_eq!(joined, vec!["abbccc".to_string(), "dddd".to_string()]);
+    }
+
+    #[test]
+    #[should_panic(expected = "group size must be greater than zero")]
+    fn downsample_zero_size_panics() {
+        let _ = vec![1.0].into_iter().downsample(0, Aggregate::Mean);
+    }
+
+    #[test]
+    fn interpolate_linear_fills_interior() {
+        let arr = vec![Some(0), None, Some(4), None, None, None, Some(0)];
+        let filled: Vec<_> = arr.into_iter().interpolate_linear().collect();
+        assert_eq!(
+            filled,
+            vec![
+                Some(0.0),
+                Some(2.0),
+                Some(4.0),
+                Some(3.0),
+                Some(2.0),
+                Some(1.0),
+                Some(0.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn interpolate_linear_edges_and_all_missing() {
+        let arr: Vec<Option<f64>> = vec![None, None];
+        let filled: Vec<_> = arr.into_iter().interpolate_linear().collect();
+        assert_eq!(filled, vec![None, None]);
+
+        let empty: Vec<Option<f64>> = vec![];
+        assert_eq!(empty.into_iter().interpolate_linear().next(), None);
+    }
+
+    #[test]
+    fn interpolate_linear_yields_leading_missing_immediately() {
+        let pulled = std::cell::Cell::new(0);
+        let mut filled = (0..)
+            .map(|i| (i >= 1000).then_some(i as f64))
+            .inspect(|_| pulled.set(pulled.get() + 1))
+            .interpolate_linear();
+        assert_eq!(filled.next(), Some(None));
+        assert_eq!(filled.next(), Some(None));
+        assert_eq!(pulled.get(), 2);
+    }
+
+    #[test]
+    fn interpolate_linear_trailing_missing() {
+        let arr = vec![None, Some(1.0), None, Some(3.0), None, None];
+        let filled: Vec<_> = arr.into_iter().interpolate_linear().collect();
+        assert_eq!(
+            filled,
+            vec![None, Some(1.0), Some(2.0), Some(3.0), None, None]
+        );
+    }
+
+    #[test]
+    fn interpolate_linear_xy_leading_missing_not_buffered() {
+        let points = vec![(0.0, None), (1.0, None), (2.0, Some(5.0))];
+        let mut iter = points.into_iter().interpolate_linear_xy();
+        assert_eq!(iter.next(), Some((0.0, None)));
+        assert_eq!(iter.size_hint(), (2, Some(2)));
+    }
+
+    #[test]
+    fn interpolate_linear_size_hint() {
+        let arr = vec![Some(1.0), None, None, Some(2.0)];
+        let mut iter = arr.into_iter().interpolate_linear();
+        assert_eq!(iter.size_hint(), (4, Some(4)));
+        iter.next();
+        iter.next();
+        assert_eq!(iter.size_hint(), (2, Some(2)));
+    }
+
+    #[test]
+    fn interpolate_linear_xy_irregular() {
+        let points = vec![
+            (1, None),
+            (2, Some(0.0)),
+            (3, None),
+            (6, None),
+            (10, Some(8.0)),
+        ];
+        let filled: Vec<_> = points.into_iter().interpolate_linear_xy().collect();
+        assert_eq!(
+            filled,
+            vec![
+                (1.0, None),
+                (2.0, Some(0.0)),
+                (3.0, Some(1.0)),
+                (6.0, Some(4.0)),
+                (10.0, Some(8.0))
+            ]
+        );
+    }
+
+    #[test]
+    fn forward_fill_basic() {
+        let arr = vec![Some(1), None, None, Some(2), None];
+        let filled: Vec<_> = arr.into_iter().forward_fill().collect();
+        assert_eq!(filled, vec![Some(1), Some(1), Some(1), Some(2), Some(2)]);
+    }
+
+    #[test]
+    fn forward_fill_leading_none() {
+        let arr: Vec<Option<String>> = vec![None, Some("a".to_string())];
+        let filled: Vec<_> = arr.into_iter().forward_fill().collect();
+        assert_eq!(filled, vec![None, Some("a".to_string())]);
+    }
+
+    #[test]
+    fn backward_fill_basic() {
+        let arr = vec![None, Some(1), None, None, Some(2)];
+        let filled: Vec<_> = arr.into_iter().backward_fill().collect();
+        assert_eq!(filled, vec![Some(1), Some(1), Some(2), Some(2), Some(2)]);
+    }
+
+    #[test]
+    fn backward_fill_trailing_and_empty() {
+        let arr: Vec<Option<i32>> = vec![Some(4), None, None];
+        let filled: Vec<_> = arr.into_iter().backward_fill().collect();
+        assert_eq!(filled, vec![Some(4), None, None]);
+
+        let empty: Vec<Option<i32>> = vec![];
+        assert_eq!(empty.into_iter().backward_fill().next(), None);
+    }
+
+    #[test]
+    fn backward_fill_size_hint() {
+        let arr = vec![None, None, Some(1), Some(2)];
+        let mut iter = arr.into_iter().backward_fill();
+        assert_eq!(iter.size_hint(), (4, Some(4)));
+        iter.next();
+        assert_eq!(iter.size_hint(), (3, Some(3)));
+    }
+
+    #[test]
+    fn peaks_basic_and_edges() {
+        let arr = vec![3, 1, 2, 1, 4];
+        assert_eq!(arr.into_iter().peaks(PeakOptions::default()), vec![2]);
+    }
+
+    #[test]
+    fn peaks_plateau_reports_first_position() {
+        let arr = vec![0.0, 2.0, 2.0, 2.0, 1.0, 3.0, 3.0];
+        assert_eq!(arr.into_iter().peaks(PeakOptions::default()), vec![1]);
+    }
+
+    #[test]
+    fn peaks_ignore_nan() {
+        let arr = vec![0.0, f64::NAN, 0.0, 1.0, f64::NAN];
+        assert!(arr.into_iter().peaks(PeakOptions::default()).is_empty());
+    }
+
+    #[test]
+    fn peaks_min_prominence() {
+        let arr = vec![0.0, 10.0, 9.0, 9.5, 0.0];
+        let options = PeakOptions {
+            min_prominence: 1.0,
+            ..PeakOptions::default()
+        };
+        assert_eq!(
+            arr.iter().copied().peaks(PeakOptions::default()),
+            vec![1, 3]
+        );
+        assert_eq!(arr.into_iter().peaks(options), vec![1]);
+    }
+
+    #[test]
+    fn peaks_min_distance_keeps_tallest() {
+        let arr = vec![0, 3, 0, 5, 0, 4, 0, 0, 0, 2, 0];
+        let options = PeakOptions {
+            min_distance: 3,
+            ..PeakOptions::default()
+        };
+        assert_eq!(arr.into_iter().peaks(options), vec![3, 9]);
+    }
+
+    #[test]
+    fn troughs_basic() {
+        let arr = vec![5.0, 1.0, 3.0, 0.5, 2.0];
+        assert_eq!(arr.into_iter().troughs(PeakOptions::default()), vec![1, 3]);
+    }
+
+    #[test]
+    fn monotone_runs_by_partial_key_basic() {
+        let arr = vec![3, 3, 1, 0, 2, 4, 4, 1];
+        let runs: Vec<_> = arr
+            .into_iter()
+            .monotone_runs_by_partial_key(|&x| x)
+            .collect();
+        assert_eq!(
+            runs,
+            vec![
+                (Trend::Falling, vec![3, 3, 1, 0]),
+                (Trend::Rising, vec![2, 4, 4]),
+                (Trend::Flat, vec![1]),
+            ]
+        );
+    }
+
+    #[test]
+    fn monotone_runs_by_partial_key_flat_and_empty() {
+        let arr = vec![7, 7, 7];
+        let runs: Vec<_> = arr
+            .into_iter()
+            .monotone_runs_by_partial_key(|&x| x)
+            .collect();
+        assert_eq!(runs, vec![(Trend::Flat, vec![7, 7, 7])]);
+
+        let empty: Vec<i32> = vec![];
+        assert_eq!(
+            empty
+                .into_iter()
+                .monotone_runs_by_partial_key(|&x| x)
+                .next(),
+            None
+        );
+    }
+
+    #[test]
+    fn monotone_runs_by_partial_key_nan_splits() {
+        let arr = vec![1.0, 2.0, f64::NAN, 3.0, 4.0];
+        let runs: Vec<_> = arr
+            .into_iter()
+            .monotone_runs_by_partial_key(|&x| x)
+            .collect();
+        let sizes: Vec<_> = runs
+            .iter()
+            .map(|(trend, run)| (*trend, run.len()))
+            .collect();
+        assert_eq!(
+            sizes,
+            vec![(Trend::Rising, 2), (Trend::Flat, 1), (Trend::Rising, 2)]
+        );
+    }
+
+    #[test]
+    fn longest_run_by_key_basic() {
+        let arr = vec![1, 1, 2, 2, 2, 1, 3, 3, 3];
+        assert_eq!(arr.iter().longest_run_by_key(|&&x| x), Some((2, 3)));
+    }
+
+    #[test]
+    fn longest_run_by_key_derived_key_and_empty() {
+        let words = vec![
+            "apple",
+            "avocado",
+            "banana",
+            "blueberry",
+            "blackberry",
+            "cherry",
+        ];
+        assert_eq!(
+            words.iter().longest_run_by_key(|w| w.chars().next()),
+            Some((2, 3))
+        );
+
+        let empty: Vec<i32> = vec![];
+        assert_eq!(empty.iter().longest_run_by_key(|&&x| x), None);
+    }
+
+    #[test]
+    fn longest_run_by_predicate() {
+        let arr = vec![1, 2, 4, 6, 7, 8, 10];
+        assert_eq!(arr.iter().longest_run_by(|&&x| x % 2 == 0), Some((1, 3)));
+        assert_eq!(arr.iter().longest_run_by(|&&x| x > 100), None);
+    }
+
+    #[test]
+    fn crossings_basic() {
+        let arr = vec![5, 12, 15, 8, 10, 3];
+        let result: Vec<_> = arr.into_iter().crossings(10.0).collect();
+        assert_eq!(
+            result,
+            vec![
+                (1, Crossing::Upward),
+                (3, Crossing::Downward),
+                (4, Crossing::Upward),
+                (5, Crossing::Downward)
+            ]
+        );
+    }
+
+    #[test]
+    fn crossings_skip_nan_and_start_above() {
+        let arr = vec![f64::NAN, 3.0, f64::NAN, 1.0, 2.0];
+        let result: Vec<_> = arr.into_iter().crossings(2.0).collect();
+        assert_eq!(result, vec![(3, Crossing::Downward), (4, Crossing::Upward)]);
+    }
+
+    #[test]
+    fn crossings_with_hysteresis_suppresses_chatter() {
+        let arr = vec![0.0, 0.4, -0.4, 0.6, 0.2, -0.2, -0.6];
+        let plain: Vec<_> = arr.iter().copied().crossings(0.0).collect();
+        let damped: Vec<_> = arr
+            .into_iter()
+            .crossings_with_hysteresis(0.0, 0.5)
+            .collect();
+        assert_eq!(plain.len(), 3);
+        assert_eq!(damped, vec![(6, Crossing::Downward)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "hysteresis must be non-negative")]
+    fn crossings_with_negative_hysteresis_panics() {
+        let _ = vec![1.0].into_iter().crossings_with_hysteresis(0.0, -1.0);
+    }
+
+    #[test]
+    fn bucket_by_integers() {
+        let arr = vec![0, 3, 9, 10, 25, 29];
+        let buckets: Vec<_> = arr.into_iter().bucket_by(|&x| x, 10).collect();
+        assert_eq!(
+            buckets,
+            vec![(0, vec![0, 3, 9]), (10, vec![10]), (20, vec![25, 29])]
+        );
+    }
+
+    #[test]
+    fn bucket_by_negative_and_float_keys() {
+        let arr = vec![-7, -5, -1, 0];
+        let buckets: Vec<_> = arr.into_iter().bucket_by(|&x| x, 5).collect();
+        assert_eq!(
+            buckets,
+            vec![(-10, vec![-7]), (-5, vec![-5, -1]), (0, vec![0])]
+        );
+
+        let times = vec![0.1, 0.4, 0.6];
+        let buckets: Vec<_> = times.into_iter().bucket_by(|&t| t, 0.5).collect();
+        assert_eq!(buckets, vec![(0.0, vec![0.1, 0.4]), (0.5, vec![0.6])]);
+    }
+
+    #[test]
+    fn bucket_by_empty() {
+        let empty: Vec<u64> = vec![];
+        assert_eq!(empty.into_iter().bucket_by(|&x| x, 60).next(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "bucket width must be positive")]
+    fn bucket_by_zero_width_panics() {
+        let _ = vec![1].into_iter().bucket_by(|&x| x, 0);
+    }
+
+    #[test]
+    fn collect_some_into_sets_and_maps() {
+        use std::collections::{BTreeMap, HashSet};
+
+        let set: Option<HashSet<i32>> = vec![1, 2, 2].into_iter().collect_some();
+        assert_eq!(set, Some(HashSet::from([1, 2])));
+
+        let map: Option<BTreeMap<&str, i32>> = vec![("a", 1)].into_iter().collect_some();
+        assert_eq!(map, Some(BTreeMap::from([("a", 1)])));
+
+        let empty: Option<HashSet<i32>> = std::iter::empty().collect_some();
+        assert_eq!(empty, None);
+    }
+
+    #[test]
+    fn collect_some_into_string() {
+        let s: Option<String> = "abc".chars().collect_some();
+        assert_eq!(s.as_deref(), Some("abc"));
+        let none: Option<String> = "".chars().collect_some();
+        assert_eq!(none, None);
+    }
+
+    #[test]
+    fn collect_ok_vec_or_else_is_lazy() {
+        let mut calls = 0;
+        let ok: Result<Vec<i32>, &str> = vec![1, 2].into_iter().collect_ok_vec_or_else(|| {
+            calls += 1;
+            "empty"
+        });
+        assert_eq!(ok, Ok(vec![1, 2]));
+        assert_eq!(calls, 0);
+
+        let err: Result<Vec<i32>, String> =
+            std::iter::empty().collect_ok_vec_or_else(|| "empty".to_string());
+        assert_eq!(err, Err("empty".to_string()));
+    }
+
+    #[test]
+    fn collect_nonempty_accessors() {
+        let mut items = vec!["a", "b"].into_iter().collect_nonempty().unwrap();
+        items.push("c");
+        assert_eq!(items.len(), 3);
+        assert!(!items.is_empty());
+        assert_eq!(*items.first(), "a");
+        assert_eq!(*items.last(), "c");
+        assert_eq!(items.as_slice(), &["a", "b", "c"]);
+        assert_eq!((&items).into_iter().count(), 3);
+        assert_eq!(Vec::from(items), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn collect_nonempty_empty_and_single() {
+        assert_eq!(Vec::<u8>::new().into_iter().collect_nonempty(), None);
+        let single = std::iter::once(5).collect_nonempty().unwrap();
+        assert_eq!(single, NonEmpty::new(5));
+        assert_eq!(single.first(), single.last());
+        assert_eq!(NonEmpty::<i32>::from_vec(vec![]), None);
+    }
+
+    #[test]
+    fn collect_array_exact() {
+        let arr = (1..=4).collect_array::<4>();
+        assert_eq!(arr, Ok([1, 2, 3, 4]));
+        let empty = std::iter::empty::<u8>().collect_array::<0>();
+        assert_eq!(empty, Ok([]));
+    }
+
+    #[test]
+    fn collect_array_length_mismatch() {
+        assert_eq!(
+            (1..=2).collect_array::<3>(),
+            Err(CollectArrayError::TooFew {
+                expected: 3,
+                actual: 2
+            })
+        );
+        assert_eq!(
+            (1..).collect_array::<2>(),
+            Err(CollectArrayError::TooMany {
+                expected: 2,
+                at_least: 3
+            })
+        );
+    }
+
+    #[test]
+    fn collect_array_stops_at_first_none() {
+        let mut pulls = 0;
+        let mut values = [Some(String::from("a")), None, Some(String::from("c"))].into_iter();
+        let result = std::iter::from_fn(|| {
+            pulls += 1;
+            values.next().flatten()
+        })
+        .collect_array::<3>();
+        assert_eq!(
+            result,
+            Err(CollectArrayError::TooFew {
+                expected: 3,
+                actual: 1
+            })
+        );
+        assert_eq!(pulls, 2);
+    }
+
+    #[test]
+    fn collect_array_error_display() {
+        let err = CollectArrayError::TooFew {
+            expected: 4,
+            actual: 1,
+        };
+        assert_eq!(
+            err.to_string(),
+            "expected 4 elements but the iterator yielded only 1"
+        );
+        let err = CollectArrayError::TooMany {
+            expected: 2,
+            at_least: 3,
+        };
+        assert_eq!(
+            err.to_string(),
+            "expected 2 elements but the iterator yielded at least 3"
+        );
+    }
+
+    #[test]
+    fn collect_exact_matches() {
+        assert_eq!((0..3).collect_exact(3), Ok(vec![0, 1, 2]));
+        assert_eq!(std::iter::empty::<i32>().collect_exact(0), Ok(vec![]));
+    }
+
+    #[test]
+    fn collect_exact_mismatch() {
+        assert_eq!(
+            (0..2).collect_exact(5),
+            Err(LengthError {
+                expected: 5,
+                actual: 2
+            })
+        );
+        let err = (0..).collect_exact(2).unwrap_err();
+        assert_eq!(
+            err,
+            LengthError {
+                expected: 2,
+                actual: 3
+            }
+        );
+        assert_eq!(err.to_string(), "expected 2 elements but got more");
+    }
+
+    #[test]
+    fn collect_tuple_arities() {
+        assert_eq!((1..=2).collect_tuple(), Some((1, 2)));
+        assert_eq!((1..=3).collect_tuple(), Some((1, 2, 3)));
+        assert_eq!((1..=4).collect_tuple(), Some((1, 2, 3, 4)));
+    }
+
+    #[test]
+    fn collect_tuple_wrong_length() {
+        assert_eq!((1..=1).collect_tuple::<(i32, i32)>(), None);
+        assert_eq!((1..=3).collect_tuple::<(i32, i32)>(), None);
+        assert_eq!((1..).collect_tuple::<(i32, i32, i32)>(), None);
+    }
+
+    #[test]
+    fn collect_tuple_non_clone_items() {
+        let items = vec![String::from("x"), String::from("y")];
+        let (a, b) = items.into_iter().collect_tuple().unwrap();
+        assert_eq!(a + &b, "xy");
+    }
+
+    #[cfg(feature = "smallvec")]
+    #[test]
+    fn collect_smallvec_inline_and_spilled() {
+        let inline = (0..3).collect_smallvec::<4>();
+        assert_eq!(inline.as_slice(), &[0, 1, 2]);
+        assert!(!inline.spilled());
+
+        let spilled = (0..10).collect_smallvec::<4>();
+        assert_eq!(spilled.len(), 10);
+        assert!(spilled.spilled());
+    }
+
+    #[cfg(feature = "smallvec")]
+    #[test]
+    fn collect_some_smallvec_empty() {
+        assert!(
+            std::iter::empty::<u8>()
+                .collect_some_smallvec::<2>()
+                .is_none()
+        );
+        let generic: Option<smallvec::SmallVec<[u8; 2]>> = std::iter::once(1).collect_some();
+        assert_eq!(generic.map(|v| v.len()), Some(1));
+    }
+
+    #[cfg(feature = "arrayvec")]
+    #[test]
+    fn collect_arrayvec_fits() {
+        let items = (1..=3).collect_arrayvec::<3>().unwrap();
+        assert_eq!(items.as_slice(), &[1, 2, 3]);
+        assert!(
+            std::iter::empty::<u8>()
+                .collect_arrayvec::<0>()
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[cfg(feature = "arrayvec")]
+    #[test]
+    fn collect_arrayvec_overflow_stops_early() {
+        let mut source = 0..10;
+        let err = source.by_ref().collect_arrayvec::<2>().unwrap_err();
+        assert_eq!(err.element(), 2);
+        assert_eq!(source.next(), Some(3));
+    }
+
+    #[test]
+    fn collect_vec_basic() {
+        assert_eq!("abc".chars().collect_vec(), vec!['a', 'b', 'c']);
+        assert!(std::iter::empty::<i32>().collect_vec().is_empty());
+    }
+
+    #[test]
+    fn collect_hashmap_policies() {
+        let pairs = vec![("a", 1), ("b", 2), ("a", 3)];
+
+        let first = pairs
+            .iter()
+            .copied()
+            .collect_hashmap(DuplicateKeyPolicy::KeepFirst);
+        assert_eq!(first, std::collections::HashMap::from([("a", 1), ("b", 2)]));
+
+        let last = pairs
+            .iter()
+            .copied()
+            .collect_hashmap(DuplicateKeyPolicy::KeepLast);
+        assert_eq!(last, std::collections::HashMap::from([("a", 3), ("b", 2)]));
+    }
+
+    #[test]
+    fn collect_hashmap_with_merge_order() {
+        let pairs = vec![
+            (1, "x".to_string()),
+            (1, "y".to_string()),
+            (1, "z".to_string()),
+        ];
+        let merged = pairs
+            .into_iter()
+            .collect_hashmap_with(|stored, value| stored.push_str(&value));
+        assert_eq!(merged[&1], "xyz");
+    }
+
+    #[test]
+    fn try_collect_unique_hashmap_ok() {
+        let map = (0..3)
+            .map(|i| (i, i * 10))
+            .try_collect_unique_hashmap()
+            .unwrap();
+        assert_eq!(map.len(), 3);
+        assert_eq!(map[&2], 20);
+    }
+
+    #[test]
+    fn try_collect_unique_hashmap_duplicate() {
+        let pairs = vec![
+            ("a".to_string(), 1),
+            ("b".to_string(), 2),
+            ("a".to_string(), 3),
+        ];
+        let err = pairs.into_iter().try_collect_unique_hashmap().unwrap_err();
+        assert_eq!(err.key, "a");
+        assert_eq!(err.index, 2);
+        assert_eq!(err.to_string(), "duplicate key \"a\" at position 2");
+    }
+
+    #[test]
+    fn collect_multimap_groups_values() {
+        let pairs = vec![(1, 'a'), (2, 'b'), (1, 'c'), (1, 'd')];
+        let map = pairs.into_iter().collect_multimap();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map[&1], vec!['a', 'c', 'd']);
+        assert_eq!(map[&2], vec!['b']);
+    }
+
+    #[test]
+    fn collect_btree_multimap_sorted_keys() {
+        let pairs = vec![("z", 1), ("a", 2), ("z", 3)];
+        let map = pairs.into_iter().collect_btree_multimap();
+        assert_eq!(map.keys().copied().collect::<Vec<_>>(), vec!["a", "z"]);
+        assert_eq!(map["z"], vec![1, 3]);
+
+        let empty = std::iter::empty::<(i32, i32)>().collect_btree_multimap();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn collect_some_btreemap_and_btreeset() {
+        let map = vec![(2, 'b'), (1, 'a'), (2, 'c')]
+            .into_iter()
+            .collect_some_btreemap()
+            .unwrap();
+        assert_eq!(
+            map.into_iter().collect::<Vec<_>>(),
+            vec![(1, 'a'), (2, 'c')]
+        );
+
+        let set = "hello".chars().collect_some_btreeset().unwrap();
+        assert_eq!(set.into_iter().collect::<String>(), "ehlo");
+
+        assert!("".chars().collect_some_btreeset().is_none());
+        assert!(
+            std::iter::empty::<(i32, i32)>()
+                .collect_some_btreemap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn collect_counted_set_order_and_counts() {
+        let arr = vec![3, 1, 3, 2, 1, 3];
+        assert_eq!(
+            arr.into_iter().collect_counted_set(),
+            vec![(3, 3), (1, 2), (2, 1)]
+        );
+    }
+
+    #[test]
+    fn collect_counted_set_empty_and_unique() {
+        assert!(std::iter::empty::<u8>().collect_counted_set().is_empty());
+        let letters = "abc".chars().collect_counted_set();
+        assert_eq!(letters, vec![('a', 1), ('b', 1), ('c', 1)]);
+    }
+
+    #[test]
+    fn partition_map_changes_types() {
+        let arr = vec![1, -2, 3, -4];
+        let (positive, negative): (Vec<u32>, Vec<String>) = arr.into_iter().partition_map(|x| {
+            if x > 0 {
+                Either::Left(x as u32)
+            } else {
+                Either::Right(x.to_string())
+            }
+        });
+        assert_eq!(positive, vec![1, 3]);
+        assert_eq!(negative, vec!["-2".to_string(), "-4".to_string()]);
+    }
+
+    #[test]
+    fn partition_map_empty_side() {
+        let (left, right): (Vec<i32>, Vec<i32>) = (0..3).partition_map(Either::Left);
+        assert_eq!(left, vec![0, 1, 2]);
+        assert!(right.is_empty());
+    }
+
+    #[test]
+    fn partition_into_buckets_basic() {
+        let words = vec!["a", "bb", "cc", "ddd", "e"];
+        let buckets = words.into_iter().partition_into_buckets(4, |w| w.len());
+        assert_eq!(
+            buckets,
+            vec![vec![], vec!["a", "e"], vec!["bb", "cc"], vec!["ddd"]]
+        );
+    }
+
+    #[test]
+    fn partition_into_buckets_zero_buckets_empty_input() {
+        let buckets = std::iter::empty::<i32>().partition_into_buckets(0, |_| 0);
+        assert!(buckets.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "bucket index 2 is out of range for 2 buckets")]
+    fn partition_into_buckets_out_of_range_panics() {
+        let _ = (0..5).partition_into_buckets(2, |&x| x as usize);
+    }
+
+    #[test]
+    fn partition_result_splits() {
+        let arr: Vec<Result<i32, &str>> = vec![Ok(1), Err("a"), Ok(2), Err("b")];
+        let (oks, errs) = arr.into_iter().partition_result();
+        assert_eq!(oks, vec![1, 2]);
+        assert_eq!(errs, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn partition_result_all_ok() {
+        let (oks, errs): (Vec<u8>, Vec<()>) = (0..3u8).map(Ok).partition_result();
+        assert_eq!(oks, vec![0, 1, 2]);
+        assert!(errs.is_empty());
+    }
+
+    #[test]
+    fn split_on_matches_slice_split() {
+        let arr = vec![0, 1, 0, 0, 2, 0];
+        let segments: Vec<_> = arr.iter().copied().split_on(|&x| x == 0).collect();
+        let expected: Vec<Vec<i32>> = arr.split(|&x| x == 0).map(|s| s.to_vec()).collect();
+        assert_eq!(segments, expected);
+
+        let empty: Vec<Vec<i32>> = Vec::new().into_iter().split_on(|&x: &i32| x == 0).collect();
+        assert_eq!(empty, vec![Vec::<i32>::new()]);
+    }
+
+    #[test]
+    fn split_on_with_keep_delimiters_matches_split_inclusive() {
+        let arr = vec![1, 0, 2, 0, 0];
+        let options = SplitOptions {
+            keep_delimiters: true,
+            ..Default::default()
+        };
+        let segments: Vec<_> = arr
+            .iter()
+            .copied()
+            .split_on_with(|&x| x == 0, options)
+            .collect();
+        let expected: Vec<Vec<i32>> = arr
+            .split_inclusive(|&x| x == 0)
+            .map(|s| s.to_vec())
+            .collect();
+        assert_eq!(segments, expected);
+    }
+
+    #[test]
+    fn split_on_with_skip_empty() {
+        let options = SplitOptions {
+            skip_empty: true,
+            ..Default::default()
+        };
+        let segments: Vec<_> = vec![0, 0, 5, 0, 6, 7, 0]
+            .into_iter()
+            .split_on_with(|&x| x == 0, options)
+            .collect();
+        assert_eq!(segments, vec![vec![5], vec![6, 7]]);
+        assert_eq!(
+            std::iter::empty::<i32>()
+                .split_on_with(|_| true, options)
+                .next(),
+            None
+        );
+    }
+
+    #[test]
+    fn distribute_round_robin_basic() {
+        let outputs = (0..7).distribute_round_robin(3);
+        assert_eq!(outputs, vec![vec![0, 3, 6], vec![1, 4], vec![2, 5]]);
+        let sparse = (0..1).distribute_round_robin(3);
+        assert_eq!(sparse, vec![vec![0], vec![], vec![]]);
+    }
+
+    #[test]
+    fn distribute_weighted_with_zero_weight() {
+        let outputs = (0..6).distribute_weighted(&[2, 0, 1]);
+        assert_eq!(outputs, vec![vec![0, 1, 3, 4], vec![], vec![2, 5]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "number of outputs must be greater than zero")]
+    fn distribute_round_robin_zero_panics() {
+        let _ = (0..3).distribute_round_robin(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one weight must be greater than zero")]
+    fn distribute_weighted_all_zero_panics() {
+        let _ = (0..3).distribute_weighted(&[0, 0]);
+    }
+
+    #[test]
+    fn filter_ok_keeps_errors() {
+        let arr: Vec<Result<i32, &str>> = vec![Ok(1), Ok(2), Err("e"), Ok(3), Ok(4)];
+        let result: Vec<_> = arr.into_iter().filter_ok(|&x| x % 2 == 0).collect();
+        assert_eq!(result, vec![Ok(2), Err("e"), Ok(4)]);
+    }
+
+    #[test]
+    fn map_ok_changes_type() {
+        let arr: Vec<Result<i32, &str>> = vec![Ok(1), Err("e"), Ok(3)];
+        let mapped: Vec<Result<String, &str>> = arr.into_iter().map_ok(|x| x.to_string()).collect();
+        assert_eq!(
+            mapped,
+            vec![Ok("1".to_string()), Err("e"), Ok("3".to_string())]
+        );
+    }
+
+    #[test]
+    fn map_ok_then_collect_result() {
+        let arr: Vec<Result<i32, &str>> = vec![Ok(1), Ok(2)];
+        let total: Result<Vec<i32>, &str> = arr.into_iter().map_ok(|x| x * 10).collect();
+        assert_eq!(total, Ok(vec![10, 20]));
+    }
+
+    #[test]
+    fn map_err_items_converts_errors() {
+        let arr: Vec<Result<i32, u8>> = vec![Ok(1), Err(2), Ok(3), Err(4)];
+        let result: Vec<Result<i32, String>> =
+            arr.into_iter().map_err_items(|e| format!("E{e}")).collect();
+        assert_eq!(
+            result,
+            vec![Ok(1), Err("E2".to_string()), Ok(3), Err("E4".to_string())]
+        );
+    }
+
+    #[test]
+    fn flatten_ok_preserves_error_positions() {
+        let arr: Vec<Result<Vec<i32>, char>> = vec![Ok(vec![]), Err('a'), Ok(vec![1, 2]), Err('b')];
+        let flat: Vec<_> = arr.into_iter().flatten_ok().collect();
+        assert_eq!(flat, vec![Err('a'), Ok(1), Ok(2), Err('b')]);
+    }
+
+    #[test]
+    fn flatten_ok_options_and_size_hint() {
+        let arr: Vec<Result<Option<u8>, ()>> = vec![Ok(Some(1)), Ok(None), Ok(Some(2))];
+        let mut iter = arr.into_iter().flatten_ok();
+        assert_eq!(iter.size_hint(), (0, None));
+        assert_eq!(iter.by_ref().collect::<Vec<_>>(), vec![Ok(1), Ok(2)]);
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn process_results_ok() {
+        let arr: Vec<Result<i32, &str>> = vec![Ok(1), Ok(2), Ok(3)];
+        let sum = arr.into_iter().process_results(|it| it.sum::<i32>());
+        assert_eq!(sum, Ok(6));
+    }
+
+    #[test]
+    fn process_results_stops_at_first_error() {
+        let arr: Vec<Result<i32, &str>> = vec![Ok(1), Err("first"), Ok(2), Err("second")];
+        let mut seen = Vec::new();
+        let result = arr.into_iter().process_results(|it| seen.extend(it));
+        assert_eq!(result, Err("first"));
+        assert_eq!(seen, vec![1]);
+    }
+
+    #[test]
+    fn process_results_partial_consumption() {
+        let arr: Vec<Result<i32, &str>> = vec![Ok(5), Err("late")];
+        let first = arr.into_iter().process_results(|mut it| it.next());
+        assert_eq!(first, Ok(Some(5)));
+    }
+
+    #[test]
+    fn while_some_stops_at_none() {
+        let arr = vec![Some(1), Some(2), None, Some(3)];
+        let mut iter = arr.into_iter().while_some();
+        assert_eq!(iter.by_ref().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn while_some_all_some() {
+        let arr = vec![Some('a'), Some('b')];
+        assert_eq!(arr.into_iter().while_some().collect::<String>(), "ab");
+    }
+
+    #[test]
+    fn try_deltas_ok_and_err() {
+        let ok: Vec<Result<char, ()>> = "abab".chars().map(Ok).collect();
+        assert_eq!(ok.into_iter().try_deltas(), Ok(vec![0, 1, 1, 1]));
+
+        let err: Vec<Result<char, u8>> = vec![Ok('a'), Err(1), Err(2)];
+        assert_eq!(err.into_iter().try_deltas(), Err(1));
+    }
+
+    #[test]
+    fn try_counts_short_circuits() {
+        let mut polled = 0;
+        let source = vec![Ok(1), Err("bad"), Ok(1)]
+            .into_iter()
+            .inspect(|_| polled += 1);
+        assert_eq!(source.try_counts(), Err("bad"));
+        assert_eq!(polled, 2);
+    }
+
+    #[test]
+    fn try_sorted_by_partial_key_sorts_ok_values() {
+        let arr: Vec<Result<(i32, char), ()>> = vec![Ok((2, 'b')), Ok((1, 'a')), Ok((2, 'c'))];
+        let sorted = arr.into_iter().try_sorted_by_partial_key(|p| p.0);
+        assert_eq!(sorted, Ok(vec![(1, 'a'), (2, 'b'), (2, 'c')]));
+    }
+
+    #[test]
+    fn fold_ok_accumulates() {
+        let arr: Vec<Result<&str, ()>> = vec![Ok("a"), Ok("b"), Ok("c")];
+        let joined = arr.into_iter().fold_ok(String::new(), |acc, s| acc + s);
+        assert_eq!(joined, Ok("abc".to_string()));
+    }
+
+    #[test]
+    fn fold_ok_stops_at_first_error() {
+        let mut iter = vec![Ok(1), Err("x"), Ok(2)].into_iter();
+        assert_eq!(iter.by_ref().fold_ok(0, |a, b| a + b), Err("x"));
+        assert_eq!(iter.next(), Some(Ok(2)));
+    }
+
+    #[test]
+    fn try_min_max_by_partial_key_ok() {
+        let arr: Vec<Result<f64, ()>> = vec![Ok(2.0), Ok(f64::NAN), Ok(-1.0), Ok(5.0)];
+        assert_eq!(
+            arr.iter().cloned().try_min_by_partial_key(|&x| x),
+            Ok(Some(-1.0))
+        );
+        assert_eq!(
+            arr.into_iter().try_max_by_partial_key(|&x| x),
+            Ok(Some(5.0))
+        );
+    }
+
+    #[test]
+    fn try_min_max_by_partial_key_empty_and_err() {
+        let empty: Vec<Result<i32, ()>> = vec![];
+        assert_eq!(empty.into_iter().try_min_by_partial_key(|&x| x), Ok(None));
+
+        let arr: Vec<Result<i32, &str>> = vec![Ok(1), Err("boom"), Ok(0)];
+        assert_eq!(arr.into_iter().try_min_by_partial_key(|&x| x), Err("boom"));
+    }
+
+    #[test]
+    fn fold_while_stops_early() {
+        let mut iter = 1..10;
+        let result = iter.by_ref().fold_while(0, |acc, x| {
+            if x == 4 {
+                FoldWhile::Done(acc)
+            } else {
+                FoldWhile::Continue(acc + x)
+            }
+        });
+        assert_eq!(result, FoldWhile::Done(6));
+        assert!(result.is_done());
+        assert_eq!(iter.next(), Some(5));
+    }
+
+    #[test]
+    fn fold_while_runs_to_completion() {
+        let result = (1..=4).fold_while(1, |acc, x| FoldWhile::Continue(acc * x));
+        assert!(!result.is_done());
+        assert_eq!(result.into_inner(), 24);
+        let empty = std::iter::empty::<i32>().fold_while(7, |acc, _| FoldWhile::Done(acc));
+        assert_eq!(empty, FoldWhile::Continue(7));
+    }
+
+    #[test]
+    fn find_last_basic() {
+        let arr = vec![1, 4, 3, 8, 5];
+        assert_eq!(arr.iter().find_last(|&&x| x % 2 == 0), Some(&8));
+        assert_eq!(arr.iter().find_last(|&&x| x > 10), None);
+    }
+
+    #[test]
+    fn find_last_single_pass_iterator() {
+        let mut n = 0;
+        let counter = std::iter::from_fn(|| {
+            n += 1;
+            (n <= 6).then_some(n)
+        });
+        assert_eq!(counter.find_last(|x| x % 4 == 0), Some(4));
+    }
+
+    #[test]
+    fn find_map_last_basic() {
+        let arr = vec!["1", "x", "2", "y"];
+        assert_eq!(arr.iter().find_map_last(|s| s.parse::<i32>().ok()), Some(2));
+        assert_eq!(
+            std::iter::empty::<&str>().find_map_last(|s| s.parse::<i32>().ok()),
+            None
+        );
+    }
+
+    #[test]
+    fn rfind_last_stops_at_last_match() {
+        let mut visited = 0;
+        let found = (1..=10)
+            .inspect(|_| visited += 1)
+            .rfind_last(|x| x % 4 == 0);
+        assert_eq!(found, Some(8));
+        assert_eq!(visited, 3);
+        assert_eq!((1..=10).rfind_last(|&x| x > 10), None);
+    }
+
+    #[test]
+    fn rfind_map_last_matches_find_map_last() {
+        let arr = ["1", "x", "2", "y"];
+        assert_eq!(
+            arr.iter().rfind_map_last(|s| s.parse::<i32>().ok()),
+            arr.iter().find_map_last(|s| s.parse::<i32>().ok())
+        );
+        assert_eq!(
+            arr.iter().rfind_map_last(|s| s.parse::<f32>().ok()),
+            Some(2.0)
+        );
+    }
+
+    #[test]
+    fn exactly_one_cases() {
+        assert_eq!(std::iter::once(7).exactly_one(), Ok(7));
+        assert_eq!(
+            std::iter::empty::<i32>().exactly_one(),
+            Err(ExactlyOneError::Empty)
+        );
+        assert_eq!(
+            (1..).exactly_one(),
+            Err(ExactlyOneError::MoreThanOne {
+                first: 1,
+                second: 2
+            })
+        );
+    }
+
+    #[test]
+    fn exactly_one_error_display() {
+        let err = (1..3).exactly_one().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "expected exactly one element but got more than one"
+        );
+    }
+
+    #[test]
+    fn at_most_one_cases() {
+        assert_eq!(std::iter::empty::<u8>().at_most_one(), Ok(None));
+        assert_eq!(std::iter::once('x').at_most_one(), Ok(Some('x')));
+        assert_eq!(
+            "abc".chars().at_most_one(),
+            Err(MoreThanOne {
+                first: 'a',
+                second: 'b'
+            })
+        );
+    }
+
+    #[test]
+    fn at_most_one_consumes_at_most_two() {
+        let mut iter = 0..10;
+        assert!(iter.by_ref().at_most_one().is_err());
+        assert_eq!(iter.next(), Some(2));
+    }
+
+    #[test]
+    fn contains_by_key_early_exit() {
+        let mut calls = 0;
+        let found = (1..100).contains_by_key(
+            |&x| {
+                calls += 1;
+                x * x
+            },
+            16,
+        );
+        assert!(found);
+        assert_eq!(calls, 4);
+    }
+
+    #[test]
+    fn find_by_key_first_match() {
+        let words = vec!["apple", "bob", "cat", "dog"];
+        assert_eq!(words.iter().find_by_key(|w| w.len(), 3), Some(&"bob"));
+        assert_eq!(words.iter().find_by_key(|w| w.len(), 10), None);
+    }
+
+    #[test]
+    fn count_if_and_count_partitioned() {
+        let data = vec![1, 2, 3, 4, 5, 6, 7];
+        assert_eq!(data.iter().count_if(|&&x| x > 4), 3);
+        assert_eq!(data.iter().count_partitioned(|&&x| x > 4), (3, 4));
+        assert_eq!(
+            std::iter::empty::<i32>().count_partitioned(|_| true),
+            (0, 0)
+        );
+    }
+
+    #[test]
+    fn sum_by_key_groups_values() {
+        let sales = vec![("north", 1.5), ("south", 2.0), ("north", 3.0)];
+        let totals = sales.iter().sum_by_key(|(r, _)| *r, |(_, v)| *v);
+        assert_eq!(totals.len(), 2);
+        assert_eq!(totals["north"], 4.5);
+        assert_eq!(totals["south"], 2.0);
+        assert!(
+            std::iter::empty::<(u8, u8)>()
+                .sum_by_key(|p| p.0, |p| p.1)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn fold_by_key_collects_per_group() {
+        let words = vec!["apple", "bean", "avocado", "beet", "cherry"];
+        let grouped = words.iter().fold_by_key(
+            |w| w.chars().next().unwrap(),
+            Vec::new,
+            |acc, w| acc.push(*w),
+        );
+        assert_eq!(grouped[&'a'], vec!["apple", "avocado"]);
+        assert_eq!(grouped[&'b'], vec!["bean", "beet"]);
+        assert_eq!(grouped[&'c'], vec!["cherry"]);
+    }
+
+    #[test]
+    fn max_by_partial_key_per_group_ties_and_nan() {
+        let data = vec![
+            (1, 2.0, 'a'),
+            (1, 2.0, 'b'),
+            (2, f64::NAN, 'c'),
+            (2, 1.0, 'd'),
+            (1, 0.5, 'e'),
+        ];
+        let best = data.iter().max_by_partial_key_per_group(|t| t.0, |t| t.1);
+        assert_eq!(best.len(), 2);
+        assert_eq!(best[&1].2, 'b');
+        assert_eq!(best[&2].2, 'd');
+        assert!(
+            std::iter::empty::<(u8, f64)>()
+                .max_by_partial_key_per_group(|t| t.0, |t| t.1)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn positions_is_lazy_over_infinite_iterator() {
+        let squares: Vec<usize> = (0u32..)
+            .positions(|&x| (x as f64).sqrt().fract() == 0.0)
+            .take(4)
+            .collect();
+        assert_eq!(squares, vec![0, 1, 4, 9]);
+        assert_eq!(vec![1, 2, 3].into_iter().positions(|_| false).count(), 0);
+    }
+
+    #[test]
+    fn index_of_by_partial_key_stops_early() {
+        let mut calls = 0;
+        let index = vec![3.0, 1.5, 1.5, 2.0]
+            .into_iter()
+            .index_of_by_partial_key(
+                |&x| {
+                    calls += 1;
+                    x
+                },
+                1.5,
+            );
+        assert_eq!(index, Some(1));
+        assert_eq!(calls, 2);
+        assert_eq!(
+            std::iter::empty::<f64>().index_of_by_partial_key(|&x| x, 0.0),
+            None
+        );
+    }
+
+    #[test]
+    fn find_first_duplicate_stops_at_earliest_repeat() {
+        let mut pulled = 0;
+        let result = [1, 2, 2, 1, 5]
+            .into_iter()
+            .inspect(|_| pulled += 1)
+            .find_first_duplicate();
+        assert_eq!(result, Some((1, 2, 2)));
+        assert_eq!(pulled, 3);
+        assert_eq!(std::iter::empty::<u8>().find_first_duplicate(), None);
+    }
+
+    #[test]
+    fn find_first_duplicate_by_key_returns_repeating_element() {
+        let words = vec!["Apple", "banana", "apple", "Banana"];
+        let result = words
+            .into_iter()
+            .find_first_duplicate_by_key(|w| w.to_lowercase());
+        assert_eq!(result, Some((0, 2, "apple")));
+    }
+
+    #[test]
+    fn first_repeat_gap_stops_early() {
+        let mut pulled = 0;
+        let result = (0..)
+            .map(|x| x % 5)
+            .inspect(|_| pulled += 1)
+            .first_repeat_gap();
+        assert_eq!(result, Some((0, 4)));
+        assert_eq!(pulled, 6);
+        assert_eq!(vec![1.0, 2.0, 3.0].into_iter().first_repeat_gap(), None);
+    }
+
+    #[test]
+    fn cycle_n_size_hint_tracks_progress() {
+        let mut iter = vec![1, 2, 3].into_iter().cycle_n(2);
+        assert_eq!(iter.size_hint(), (6, Some(6)));
+        iter.next();
+        iter.next();
+        iter.next();
+        assert_eq!(iter.size_hint(), (3, Some(3)));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn cycle_n_zero_and_empty() {
+        assert_eq!((1..4).cycle_n(0).count(), 0);
+        assert_eq!((1..4).cycle_n(0).size_hint(), (0, Some(0)));
+        let mut empty = std::iter::empty::<i32>().cycle_n(usize::MAX);
+        assert_eq!(empty.next(), None);
+        assert_eq!(empty.size_hint(), (0, Some(0)));
+        assert_eq!((0..usize::MAX).cycle_n(3).size_hint(), (usize::MAX, None));
+    }
 }
@@ -98,133 +98,8616 @@ where
     }
 }
 
+pub struct WindowsRef<I: Iterator> {
+    iter: I,
+    buffer: Vec<I::Item>,
+    start: usize,
+    size: usize,
+}
+
+impl<I: Iterator> WindowsRef<I> {
+    pub(crate) fn new(iter: I, size: usize) -> Self {
+        assert!(size != 0, "window size must be non-zero");
+        WindowsRef {
+            iter,
+            buffer: Vec::with_capacity(size * 2),
+            start: 0,
+            size,
+        }
+    }
+
+    /// Advances to the next window and returns a view into it.
+    ///
+    /// The returned slice borrows the internal buffer, so it must be dropped before the
+    /// next call. Returns `None` once the underlying iterator can no longer fill a window.
+    pub fn next_window(&mut self) -> Option<&[I::Item]> {
+        if self.buffer.len() < self.size {
+            let missing = self.size - self.buffer.len();
+            self.buffer.extend(self.iter.by_ref().take(missing));
+            if self.buffer.len() < self.size {
+                return None;
+            }
+        } else {
+            let next_item = self.iter.next()?;
+            if self.buffer.len() == self.size * 2 {
+                self.buffer.drain(..self.start);
+                self.start = 0;
+            }
+            self.buffer.push(next_item);
+            self.start += 1;
+        }
+
+        Some(&self.buffer[self.start..self.start + self.size])
+    }
+}
+
+pub struct Batching<I, F> {
+    iter: I,
+    batch_fn: F,
+}
+
+impl<I, F> Batching<I, F> {
+    pub(crate) fn new(iter: I, batch_fn: F) -> Self {
+        Batching { iter, batch_fn }
+    }
+}
+
+impl<I: Iterator, B, F> Iterator for Batching<I, F>
+where
+    F: FnMut(&mut I) -> Option<B>,
+{
+    type Item = B;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        (self.batch_fn)(&mut self.iter)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Position {
+    First,
+    Middle,
+    Last,
+    Only,
+}
+
+pub struct WithPosition<I: Iterator> {
+    peekable_iter: std::iter::Peekable<I>,
+    started: bool,
+}
+
+impl<I: Iterator> WithPosition<I> {
+    pub(crate) fn new(iter: I) -> Self {
+        WithPosition {
+            peekable_iter: iter.peekable(),
+            started: false,
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for WithPosition<I> {
+    type Item = (Position, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next_item = self.peekable_iter.next()?;
+        let is_last = self.peekable_iter.peek().is_none();
+
+        let position = match (self.started, is_last) {
+            (false, true) => Position::Only,
+            (false, false) => Position::First,
+            (true, true) => Position::Last,
+            (true, false) => Position::Middle,
+        };
+        self.started = true;
+        Some((position, next_item))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.peekable_iter.size_hint()
+    }
+}
+
+pub struct Intersperse<I: Iterator> {
+    peekable_iter: std::iter::Peekable<I>,
+    separator: I::Item,
+    needs_separator: bool,
+}
+
+impl<I: Iterator> Intersperse<I> {
+    pub(crate) fn new(iter: I, separator: I::Item) -> Self {
+        Intersperse {
+            peekable_iter: iter.peekable(),
+            separator,
+            needs_separator: false,
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for Intersperse<I>
+where
+    I::Item: Clone,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.needs_separator && self.peekable_iter.peek().is_some() {
+            self.needs_separator = false;
+            return Some(self.separator.clone());
+        }
+
+        let next_item = self.peekable_iter.next()?;
+        self.needs_separator = true;
+        Some(next_item)
+    }
+}
+
+pub struct IntersperseWith<I: Iterator, F> {
+    peekable_iter: std::iter::Peekable<I>,
+    separator_fn: F,
+    needs_separator: bool,
+}
+
+impl<I: Iterator, F> IntersperseWith<I, F> {
+    pub(crate) fn new(iter: I, separator_fn: F) -> Self {
+        IntersperseWith {
+            peekable_iter: iter.peekable(),
+            separator_fn,
+            needs_separator: false,
+        }
+    }
+}
+
+impl<I: Iterator, F> Iterator for IntersperseWith<I, F>
+where
+    F: FnMut() -> I::Item,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.needs_separator && self.peekable_iter.peek().is_some() {
+            self.needs_separator = false;
+            return Some((self.separator_fn)());
+        }
+
+        let next_item = self.peekable_iter.next()?;
+        self.needs_separator = true;
+        Some(next_item)
+    }
+}
+
+pub struct FormatWith<'a, I, F> {
+    separator: &'a str,
+    inner: std::cell::Cell<Option<(I, F)>>,
+}
+
+impl<'a, I, F> FormatWith<'a, I, F> {
+    pub(crate) fn new(iter: I, separator: &'a str, format_fn: F) -> Self {
+        FormatWith {
+            separator,
+            inner: std::cell::Cell::new(Some((iter, format_fn))),
+        }
+    }
+}
+
+impl<I: Iterator, F> std::fmt::Display for FormatWith<'_, I, F>
+where
+    F: FnMut(I::Item, &mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (iter, mut format_fn) = self
+            .inner
+            .take()
+            .expect("FormatWith can only be formatted once");
+
+        for (index, item) in iter.enumerate() {
+            if index > 0 {
+                f.write_str(self.separator)?;
+            }
+            format_fn(item, f)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZipEqError {
+    pub index: usize,
+    pub left_exhausted: bool,
+}
+
+impl std::fmt::Display for ZipEqError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (shorter, longer) = if self.left_exhausted {
+            ("left", "right")
+        } else {
+            ("right", "left")
+        };
+        write!(
+            f,
+            "iterators have different lengths: {shorter} ended after {} elements while {longer} continued",
+            self.index
+        )
+    }
+}
+
+impl std::error::Error for ZipEqError {}
+
+pub struct TryZipEq<I, J> {
+    left: I,
+    right: J,
+    index: usize,
+    finished: bool,
+}
+
+impl<I, J> TryZipEq<I, J> {
+    pub(crate) fn new(left: I, right: J) -> Self {
+        TryZipEq {
+            left,
+            right,
+            index: 0,
+            finished: false,
+        }
+    }
+}
+
+impl<I: Iterator, J: Iterator> Iterator for TryZipEq<I, J> {
+    type Item = Result<(I::Item, J::Item), ZipEqError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let result = match (self.left.next(), self.right.next()) {
+            (Some(a), Some(b)) => Ok((a, b)),
+            (None, None) => {
+                self.finished = true;
+                return None;
+            }
+            (left, _) => Err(ZipEqError {
+                index: self.index,
+                left_exhausted: left.is_none(),
+            }),
+        };
+
+        self.index += 1;
+        self.finished = result.is_err();
+        Some(result)
+    }
+}
+
+pub struct ZipEq<I, J> {
+    inner: TryZipEq<I, J>,
+}
+
+impl<I, J> ZipEq<I, J> {
+    pub(crate) fn new(left: I, right: J) -> Self {
+        ZipEq {
+            inner: TryZipEq::new(left, right),
+        }
+    }
+}
+
+impl<I: Iterator, J: Iterator> Iterator for ZipEq<I, J> {
+    type Item = (I::Item, J::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.inner.next()? {
+            Ok(pair) => Some(pair),
+            Err(err) => panic!("zip_eq: {err}"),
+        }
+    }
+}
+
+pub struct ZipWith<I, J, F> {
+    left: I,
+    right: J,
+    zip_fn: F,
+}
+
+impl<I, J, F> ZipWith<I, J, F> {
+    pub(crate) fn new(left: I, right: J, zip_fn: F) -> Self {
+        ZipWith {
+            left,
+            right,
+            zip_fn,
+        }
+    }
+}
+
+impl<I: Iterator, J: Iterator, R, F> Iterator for ZipWith<I, J, F>
+where
+    F: FnMut(I::Item, J::Item) -> R,
+{
+    type Item = R;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let a = self.left.next()?;
+        let b = self.right.next()?;
+        Some((self.zip_fn)(a, b))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (left_lower, left_upper) = self.left.size_hint();
+        let (right_lower, right_upper) = self.right.size_hint();
+        let upper = match (left_upper, right_upper) {
+            (Some(l), Some(r)) => Some(l.min(r)),
+            (l, r) => l.or(r),
+        };
+        (left_lower.min(right_lower), upper)
+    }
+}
+
+pub struct Interleave<I, J> {
+    left: std::iter::Fuse<I>,
+    right: std::iter::Fuse<J>,
+    take_right: bool,
+}
+
+impl<I: Iterator, J: Iterator> Interleave<I, J> {
+    pub(crate) fn new(left: I, right: J) -> Self {
+        Interleave {
+            left: left.fuse(),
+            right: right.fuse(),
+            take_right: false,
+        }
+    }
+}
+
+impl<I, J> Iterator for Interleave<I, J>
+where
+    I: Iterator,
+    J: Iterator<Item = I::Item>,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next_item = if self.take_right {
+            self.right.next().or_else(|| self.left.next())
+        } else {
+            self.left.next().or_else(|| self.right.next())
+        };
+        self.take_right = !self.take_right;
+        next_item
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (left_lower, left_upper) = self.left.size_hint();
+        let (right_lower, right_upper) = self.right.size_hint();
+        let upper = match (left_upper, right_upper) {
+            (Some(l), Some(r)) => l.checked_add(r),
+            _ => None,
+        };
+        (left_lower.saturating_add(right_lower), upper)
+    }
+}
+
+pub struct InterleaveShortest<I, J> {
+    left: I,
+    right: J,
+    take_right: bool,
+    finished: bool,
+}
+
+impl<I, J> InterleaveShortest<I, J> {
+    pub(crate) fn new(left: I, right: J) -> Self {
+        InterleaveShortest {
+            left,
+            right,
+            take_right: false,
+            finished: false,
+        }
+    }
+}
+
+impl<I, J> Iterator for InterleaveShortest<I, J>
+where
+    I: Iterator,
+    J: Iterator<Item = I::Item>,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let next_item = if self.take_right {
+            self.right.next()
+        } else {
+            self.left.next()
+        };
+        self.take_right = !self.take_right;
+        self.finished = next_item.is_none();
+        next_item
+    }
+}
+
+struct HeadTail<J: Iterator> {
+    head: J::Item,
+    tail: J,
+    source: usize,
+}
+
+impl<J: Iterator> HeadTail<J> {
+    fn new(mut iter: J, source: usize) -> Option<Self> {
+        let head = iter.next()?;
+        Some(HeadTail {
+            head,
+            tail: iter,
+            source,
+        })
+    }
+}
+
+fn sift_down<T, F>(heap: &mut [T], mut index: usize, less_than: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    loop {
+        let left = 2 * index + 1;
+        if left >= heap.len() {
+            return;
+        }
+
+        let right = left + 1;
+        let child = if right < heap.len() && less_than(&heap[right], &heap[left]) {
+            right
+        } else {
+            left
+        };
+
+        if !less_than(&heap[child], &heap[index]) {
+            return;
+        }
+        heap.swap(child, index);
+        index = child;
+    }
+}
+
+fn heapify<T, F>(heap: &mut [T], less_than: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    for index in (0..heap.len() / 2).rev() {
+        sift_down(heap, index, less_than);
+    }
+}
+
+fn kmerge_next<J, F>(heap: &mut Vec<HeadTail<J>>, less_than: &mut F) -> Option<J::Item>
+where
+    J: Iterator,
+    F: FnMut(&HeadTail<J>, &HeadTail<J>) -> bool,
+{
+    if heap.is_empty() {
+        return None;
+    }
+
+    let next_item = match heap[0].tail.next() {
+        Some(next_head) => std::mem::replace(&mut heap[0].head, next_head),
+        None => heap.swap_remove(0).head,
+    };
+    sift_down(heap, 0, less_than);
+    Some(next_item)
+}
+
+fn kmerge_size_hint<J: Iterator>(heap: &[HeadTail<J>]) -> (usize, Option<usize>) {
+    heap.iter().fold((0, Some(0)), |(lower, upper), head_tail| {
+        let (tail_lower, tail_upper) = head_tail.tail.size_hint();
+        let upper = match (upper, tail_upper) {
+            (Some(u), Some(t)) => u.checked_add(t).and_then(|sum| sum.checked_add(1)),
+            _ => None,
+        };
+        (lower.saturating_add(tail_lower).saturating_add(1), upper)
+    })
+}
+
+fn break_tie<J: Iterator>(ordering: std::cmp::Ordering, a: &HeadTail<J>, b: &HeadTail<J>) -> bool {
+    ordering.then(a.source.cmp(&b.source)) == std::cmp::Ordering::Less
+}
+
+pub struct KMerge<J: Iterator> {
+    heap: Vec<HeadTail<J>>,
+}
+
+impl<J: Iterator> KMerge<J>
+where
+    J::Item: Ord,
+{
+    pub(crate) fn new<I: Iterator<Item = J>>(iters: I) -> Self {
+        let mut heap = (iters.enumerate())
+            .filter_map(|(source, iter)| HeadTail::new(iter, source))
+            .collect::<Vec<_>>();
+        heapify(&mut heap, &mut Self::less_than);
+        KMerge { heap }
+    }
+
+    fn less_than(a: &HeadTail<J>, b: &HeadTail<J>) -> bool {
+        break_tie(a.head.cmp(&b.head), a, b)
+    }
+}
+
+impl<J: Iterator> Iterator for KMerge<J>
+where
+    J::Item: Ord,
+{
+    type Item = J::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        kmerge_next(&mut self.heap, &mut Self::less_than)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        kmerge_size_hint(&self.heap)
+    }
+}
+
+pub struct KMergeByPartialKey<J: Iterator, F> {
+    heap: Vec<HeadTail<J>>,
+    key_fn: F,
+}
+
+impl<J: Iterator, K, F> KMergeByPartialKey<J, F>
+where
+    F: FnMut(&J::Item) -> K,
+    K: PartialOrd,
+{
+    pub(crate) fn new<I: Iterator<Item = J>>(iters: I, mut key_fn: F) -> Self {
+        let mut heap = (iters.enumerate())
+            .filter_map(|(source, iter)| HeadTail::new(iter, source))
+            .collect::<Vec<_>>();
+        heapify(&mut heap, &mut |a, b| Self::less_than(&mut key_fn, a, b));
+        KMergeByPartialKey { heap, key_fn }
+    }
+
+    fn less_than(key_fn: &mut F, a: &HeadTail<J>, b: &HeadTail<J>) -> bool {
+        let ordering = key_fn(&a.head)
+            .partial_cmp(&key_fn(&b.head))
+            .unwrap_or(std::cmp::Ordering::Equal);
+        break_tie(ordering, a, b)
+    }
+}
+
+impl<J: Iterator, K, F> Iterator for KMergeByPartialKey<J, F>
+where
+    F: FnMut(&J::Item) -> K,
+    K: PartialOrd,
+{
+    type Item = J::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let key_fn = &mut self.key_fn;
+        kmerge_next(&mut self.heap, &mut |a, b| Self::less_than(key_fn, a, b))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        kmerge_size_hint(&self.heap)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EitherOrBoth<L, R> {
+    Left(L),
+    Right(R),
+    Both(L, R),
+}
+
+pub struct MergeJoinBy<I: Iterator, J: Iterator, F> {
+    left: std::iter::Peekable<I>,
+    right: std::iter::Peekable<J>,
+    cmp_fn: F,
+}
+
+impl<I: Iterator, J: Iterator, F> MergeJoinBy<I, J, F> {
+    pub(crate) fn new(left: I, right: J, cmp_fn: F) -> Self {
+        MergeJoinBy {
+            left: left.peekable(),
+            right: right.peekable(),
+            cmp_fn,
+        }
+    }
+}
+
+impl<I: Iterator, J: Iterator, F> Iterator for MergeJoinBy<I, J, F>
+where
+    F: FnMut(&I::Item, &J::Item) -> std::cmp::Ordering,
+{
+    type Item = EitherOrBoth<I::Item, J::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let ordering = match (self.left.peek(), self.right.peek()) {
+            (Some(l), Some(r)) => (self.cmp_fn)(l, r),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => return None,
+        };
+
+        match ordering {
+            std::cmp::Ordering::Less => self.left.next().map(EitherOrBoth::Left),
+            std::cmp::Ordering::Greater => self.right.next().map(EitherOrBoth::Right),
+            std::cmp::Ordering::Equal => {
+                let l = self.left.next()?;
+                let r = self.right.next()?;
+                Some(EitherOrBoth::Both(l, r))
+            }
+        }
+    }
+}
+
+pub trait HomogeneousTuple: Sized {
+    type Item;
+    const ARITY: usize;
+
+    fn from_pool(pool: &[Self::Item], indices: &[usize]) -> Self;
+}
+
+impl<T: Clone> HomogeneousTuple for (T, T) {
+    type Item = T;
+    const ARITY: usize = 2;
+
+    fn from_pool(pool: &[T], indices: &[usize]) -> Self {
+        (pool[indices[0]].clone(), pool[indices[1]].clone())
+    }
+}
+
+impl<T: Clone> HomogeneousTuple for (T, T, T) {
+    type Item = T;
+    const ARITY: usize = 3;
+
+    fn from_pool(pool: &[T], indices: &[usize]) -> Self {
+        (
+            pool[indices[0]].clone(),
+            pool[indices[1]].clone(),
+            pool[indices[2]].clone(),
+        )
+    }
+}
+
+impl<T: Clone> HomogeneousTuple for (T, T, T, T) {
+    type Item = T;
+    const ARITY: usize = 4;
+
+    fn from_pool(pool: &[T], indices: &[usize]) -> Self {
+        (
+            pool[indices[0]].clone(),
+            pool[indices[1]].clone(),
+            pool[indices[2]].clone(),
+            pool[indices[3]].clone(),
+        )
+    }
+}
+
+pub trait TupleCollect: Sized {
+    type Item;
+
+    /// Takes one element per tuple field, returning `None` if `iter` runs out first.
+    fn take_from<I: Iterator<Item = Self::Item>>(iter: &mut I) -> Option<Self>;
+}
+
+impl<T> TupleCollect for (T, T) {
+    type Item = T;
+
+    fn take_from<I: Iterator<Item = T>>(iter: &mut I) -> Option<Self> {
+        Some((iter.next()?, iter.next()?))
+    }
+}
+
+impl<T> TupleCollect for (T, T, T) {
+    type Item = T;
+
+    fn take_from<I: Iterator<Item = T>>(iter: &mut I) -> Option<Self> {
+        Some((iter.next()?, iter.next()?, iter.next()?))
+    }
+}
+
+impl<T> TupleCollect for (T, T, T, T) {
+    type Item = T;
+
+    fn take_from<I: Iterator<Item = T>>(iter: &mut I) -> Option<Self> {
+        Some((iter.next()?, iter.next()?, iter.next()?, iter.next()?))
+    }
+}
+
+pub struct TupleCombinations<I: Iterator, T> {
+    iter: std::iter::Fuse<I>,
+    pool: Vec<I::Item>,
+    indices: Vec<usize>,
+    started: bool,
+    _tuple: std::marker::PhantomData<T>,
+}
+
+impl<I: Iterator, T: HomogeneousTuple<Item = I::Item>> TupleCombinations<I, T> {
+    pub(crate) fn new(iter: I) -> Self {
+        TupleCombinations {
+            iter: iter.fuse(),
+            pool: Vec::new(),
+            indices: (0..T::ARITY).collect(),
+            started: false,
+            _tuple: std::marker::PhantomData,
+        }
+    }
+
+    fn advance(&mut self) -> bool {
+        let arity = T::ARITY;
+        if self.indices[arity - 1] + 1 == self.pool.len() {
+            self.pool.extend(self.iter.next());
+        }
+
+        let Some(i) = (0..arity)
+            .rev()
+            .find(|&i| self.indices[i] != i + self.pool.len() - arity)
+        else {
+            return false;
+        };
+
+        self.indices[i] += 1;
+        for j in i + 1..arity {
+            self.indices[j] = self.indices[j - 1] + 1;
+        }
+        true
+    }
+}
+
+impl<I: Iterator, T: HomogeneousTuple<Item = I::Item>> Iterator for TupleCombinations<I, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+            self.pool.extend(self.iter.by_ref().take(T::ARITY));
+            if self.pool.len() < T::ARITY {
+                self.indices.clear();
+                return None;
+            }
+        } else if self.indices.is_empty() || !self.advance() {
+            self.indices.clear();
+            return None;
+        }
+
+        Some(T::from_pool(&self.pool, &self.indices))
+    }
+}
+
+/// Returns whether `a` sorts before `b` when keys are only partially ordered.
+///
+/// Keys that are not comparable with themselves (like NaN) sort after all other keys; any other
+/// incomparable pair is treated as equal.
+fn partial_key_less<K: PartialOrd>(a: &K, b: &K) -> bool {
+    match a.partial_cmp(b) {
+        Some(ordering) => ordering == std::cmp::Ordering::Less,
+        None => a.partial_cmp(a).is_some() && b.partial_cmp(b).is_none(),
+    }
+}
+
+/// Stable merge sort over positions `0..len`, ordered by the `less` comparison.
+///
+/// Unlike `slice::sort_by`, it never panics when the comparison is not a total order.
+fn stable_sort_indices(len: usize, mut less: impl FnMut(usize, usize) -> bool) -> Vec<usize> {
+    let mut indices = (0..len).collect::<Vec<_>>();
+    let mut buffer = indices.clone();
+
+    let mut width = 1;
+    while width < len {
+        for start in (0..len).step_by(2 * width) {
+            let mid = (start + width).min(len);
+            let end = (start + 2 * width).min(len);
+            let (mut left, mut right) = (start, mid);
+
+            for slot in &mut buffer[start..end] {
+                let take_right =
+                    left >= mid || (right < end && less(indices[right], indices[left]));
+                if take_right {
+                    *slot = indices[right];
+                    right += 1;
+                } else {
+                    *slot = indices[left];
+                    left += 1;
+                }
+            }
+        }
+        std::mem::swap(&mut indices, &mut buffer);
+        width *= 2;
+    }
+    indices
+}
+
+/// Stable sort over positions of `keys`, ordered as described in `partial_key_less`.
+fn sort_indices_by_partial_key<K: PartialOrd>(keys: &[K]) -> Vec<usize> {
+    stable_sort_indices(keys.len(), |a, b| partial_key_less(&keys[a], &keys[b]))
+}
+
+/// Collects every position whose key ties for the extreme selected by `wanted`.
+///
+/// Keys that compare as `wanted` against the current extreme replace it, equal keys join it,
+/// and incomparable keys are skipped.
+fn extreme_positions<T, K: PartialOrd>(
+    iter: impl Iterator<Item = T>,
+    mut key: impl FnMut(&T) -> K,
+    wanted: std::cmp::Ordering,
+) -> Vec<usize> {
+    let mut best: Option<K> = None;
+    let mut positions = Vec::new();
+    for (index, item) in iter.enumerate() {
+        let k = key(&item);
+        match best.as_ref().map(|current| k.partial_cmp(current)) {
+            None => {
+                best = Some(k);
+                positions.push(index);
+            }
+            Some(Some(ordering)) if ordering == wanted => {
+                best = Some(k);
+                positions.clear();
+                positions.push(index);
+            }
+            Some(Some(std::cmp::Ordering::Equal)) => positions.push(index),
+            Some(_) => {}
+        }
+    }
+    positions
+}
+
+/// Reorders `items` in place so that position `i` holds the element previously at
+/// `indices[i]`, following each cycle of the permutation with swaps.
+fn permute_in_place<T>(items: &mut [T], mut indices: Vec<usize>) {
+    for start in 0..indices.len() {
+        let mut current = start;
+        while indices[current] != start {
+            let source = indices[current];
+            items.swap(current, source);
+            indices[current] = current;
+            current = source;
+        }
+        indices[current] = current;
+    }
+}
+
+pub struct SortedByPartialKey<K, T> {
+    iter: std::vec::IntoIter<(K, T)>,
+}
+
+impl<K, T> SortedByPartialKey<K, T> {
+    pub(crate) fn new(keyed: Vec<(K, T)>) -> Self {
+        SortedByPartialKey {
+            iter: keyed.into_iter(),
+        }
+    }
+}
+
+impl<K, T> Iterator for SortedByPartialKey<K, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(_, item)| item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+pub struct SortedStreamByPartialKey<T, K> {
+    heap: Vec<(K, usize, T)>,
+}
+
+impl<T, K: PartialOrd> SortedStreamByPartialKey<T, K> {
+    pub(crate) fn new<I, F>(iter: I, mut key_fn: F) -> Self
+    where
+        I: Iterator<Item = T>,
+        F: FnMut(&T) -> K,
+    {
+        let mut heap = (iter.enumerate())
+            .map(|(index, item)| (key_fn(&item), index, item))
+            .collect::<Vec<_>>();
+        heapify(&mut heap, &mut Self::less_than);
+        SortedStreamByPartialKey { heap }
+    }
+
+    fn less_than(a: &(K, usize, T), b: &(K, usize, T)) -> bool {
+        let ordering = (a.0.partial_cmp(&b.0))
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.1.cmp(&b.1));
+        ordering == std::cmp::Ordering::Less
+    }
+}
+
+impl<T, K: PartialOrd> Iterator for SortedStreamByPartialKey<T, K> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.heap.is_empty() {
+            return None;
+        }
+
+        let (_, _, next_item) = self.heap.swap_remove(0);
+        sift_down(&mut self.heap, 0, &mut Self::less_than);
+        Some(next_item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.heap.len(), Some(self.heap.len()))
+    }
+}
+
+/// Quickselect over positions of `keys` that treats incomparable keys as equal.
+///
+/// Afterwards `indices[nth]` refers to the `nth` smallest key, with no greater key before it
+/// and no smaller key after it.
+fn select_nth_by_partial_key<K: PartialOrd>(keys: &[K], indices: &mut [usize], nth: usize) {
+    let (mut lo, mut hi) = (0, indices.len());
+    while hi - lo > 1 {
+        let pivot = indices[lo + (hi - lo) / 2];
+        let (mut lt, mut i, mut gt) = (lo, lo, hi);
+        while i < gt {
+            match keys[indices[i]].partial_cmp(&keys[pivot]) {
+                Some(std::cmp::Ordering::Less) => {
+                    indices.swap(lt, i);
+                    lt += 1;
+                    i += 1;
+                }
+                Some(std::cmp::Ordering::Greater) => {
+                    gt -= 1;
+                    indices.swap(i, gt);
+                }
+                _ => i += 1,
+            }
+        }
+
+        if nth < lt {
+            hi = lt;
+        } else if nth >= gt {
+            lo = gt;
+        } else {
+            return;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum QuantileInterpolation {
+    #[default]
+    Linear,
+    Lower,
+    Higher,
+    Nearest,
+    Midpoint,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RankTies {
+    #[default]
+    Average,
+    Min,
+    Max,
+    Dense,
+}
+
+fn shannon_entropy<K>(counts: &std::collections::HashMap<K, usize>) -> f64 {
+    let total = counts.values().sum::<usize>() as f64;
+    let entropy = (counts.values())
+        .map(|&count| count as f64 / total)
+        .map(|p| -p * p.log2())
+        .sum::<f64>();
+    entropy.max(0.0)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum WarmUp {
+    #[default]
+    Empty,
+    Partial,
+}
+
+pub struct RollingMean<I> {
+    iter: I,
+    window: std::collections::VecDeque<f64>,
+    size: usize,
+    warm_up: WarmUp,
+    finite_sum: crate::stats::NeumaierSum,
+    nan_count: usize,
+    pos_inf_count: usize,
+    neg_inf_count: usize,
+}
+
+impl<I> RollingMean<I> {
+    pub(crate) fn new(iter: I, size: usize, warm_up: WarmUp) -> Self {
+        assert!(size != 0, "window size must be non-zero");
+        RollingMean {
+            iter,
+            window: std::collections::VecDeque::with_capacity(size),
+            size,
+            warm_up,
+            finite_sum: crate::stats::NeumaierSum::default(),
+            nan_count: 0,
+            pos_inf_count: 0,
+            neg_inf_count: 0,
+        }
+    }
+
+    fn track(&mut self, value: f64, sign: f64) {
+        let counter = if value.is_nan() {
+            &mut self.nan_count
+        } else if value == f64::INFINITY {
+            &mut self.pos_inf_count
+        } else if value == f64::NEG_INFINITY {
+            &mut self.neg_inf_count
+        } else {
+            self.finite_sum.push(sign * value);
+            return;
+        };
+
+        if sign > 0.0 {
+            *counter += 1;
+        } else {
+            *counter -= 1;
+        }
+    }
+
+    fn current_mean(&self) -> f64 {
+        match (self.nan_count, self.pos_inf_count, self.neg_inf_count) {
+            (0, 0, 0) => self.finite_sum.total() / self.window.len() as f64,
+            (0, _, 0) => f64::INFINITY,
+            (0, 0, _) => f64::NEG_INFINITY,
+            _ => f64::NAN,
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for RollingMean<I>
+where
+    I::Item: Into<f64>,
+{
+    type Item = Option<f64>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.iter.next()?.into();
+        if self.window.len() == self.size {
+            let oldest = self.window.pop_front().expect("window is full");
+            self.track(oldest, -1.0);
+        }
+        self.window.push_back(value);
+        self.track(value, 1.0);
+
+        if self.window.len() < self.size && self.warm_up == WarmUp::Empty {
+            return Some(None);
+        }
+        Some(Some(self.current_mean()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+pub struct Ewma<I> {
+    iter: I,
+    alpha: f64,
+    average: Option<f64>,
+}
+
+impl<I> Ewma<I> {
+    pub(crate) fn new(iter: I, alpha: f64) -> Self {
+        assert!(
+            alpha > 0.0 && alpha <= 1.0,
+            "smoothing factor must be within (0, 1]"
+        );
+        Ewma {
+            iter,
+            alpha,
+            average: None,
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for Ewma<I>
+where
+    I::Item: Into<f64>,
+{
+    type Item = f64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.iter.next()?.into();
+        let average = match self.average {
+            Some(previous) => previous + self.alpha * (value - previous),
+            None => value,
+        };
+        self.average = Some(average);
+        Some(average)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+pub struct RollingExtreme<I: Iterator> {
+    iter: I,
+    candidates: std::collections::VecDeque<(usize, I::Item)>,
+    size: usize,
+    index: usize,
+    superseded: std::cmp::Ordering,
+}
+
+impl<I: Iterator> RollingExtreme<I> {
+    pub(crate) fn new(iter: I, size: usize, superseded: std::cmp::Ordering) -> Self {
+        assert!(size != 0, "window size must be non-zero");
+        RollingExtreme {
+            iter,
+            candidates: std::collections::VecDeque::new(),
+            size,
+            index: 0,
+            superseded,
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for RollingExtreme<I>
+where
+    I::Item: PartialOrd + Clone,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next_item = self.iter.next()?;
+
+        // An item that is not even comparable with itself (like NaN) never becomes a candidate.
+        let latest = if next_item.partial_cmp(&next_item).is_none() {
+            Some(next_item)
+        } else {
+            while (self.candidates.back()).is_some_and(|(_, item)| {
+                item.partial_cmp(&next_item)
+                    .is_none_or(|ordering| ordering == self.superseded)
+            }) {
+                self.candidates.pop_back();
+            }
+            self.candidates.push_back((self.index, next_item));
+            None
+        };
+
+        while (self.candidates.front()).is_some_and(|(index, _)| index + self.size <= self.index) {
+            self.candidates.pop_front();
+        }
+        self.index += 1;
+
+        match self.candidates.front() {
+            Some((_, item)) => Some(item.clone()),
+            None => latest,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+pub struct RollingExtremeByPartialKey<I: Iterator, K, F> {
+    iter: I,
+    key_fn: F,
+    candidates: std::collections::VecDeque<(usize, K, I::Item)>,
+    size: usize,
+    index: usize,
+    superseded: std::cmp::Ordering,
+}
+
+impl<I: Iterator, K, F> RollingExtremeByPartialKey<I, K, F> {
+    pub(crate) fn new(iter: I, size: usize, key_fn: F, superseded: std::cmp::Ordering) -> Self {
+        assert!(size != 0, "window size must be non-zero");
+        RollingExtremeByPartialKey {
+            iter,
+            key_fn,
+            candidates: std::collections::VecDeque::new(),
+            size,
+            index: 0,
+            superseded,
+        }
+    }
+}
+
+impl<I: Iterator, K, F> Iterator for RollingExtremeByPartialKey<I, K, F>
+where
+    I::Item: Clone,
+    K: PartialOrd,
+    F: FnMut(&I::Item) -> K,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next_item = self.iter.next()?;
+        let next_key = (self.key_fn)(&next_item);
+
+        // A key that is not even comparable with itself (like NaN) never becomes a candidate.
+        let latest = if next_key.partial_cmp(&next_key).is_none() {
+            Some(next_item)
+        } else {
+            while (self.candidates.back()).is_some_and(|(_, key, _)| {
+                key.partial_cmp(&next_key)
+                    .is_none_or(|ordering| ordering == self.superseded)
+            }) {
+                self.candidates.pop_back();
+            }
+            self.candidates.push_back((self.index, next_key, next_item));
+            None
+        };
+
+        while (self.candidates.front()).is_some_and(|(index, _, _)| index + self.size <= self.index)
+        {
+            self.candidates.pop_front();
+        }
+        self.index += 1;
+
+        match self.candidates.front() {
+            Some((_, _, item)) => Some(item.clone()),
+            None => latest,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+pub struct ZScore<I> {
+    iter: I,
+    mean: f64,
+    stddev: f64,
+}
+
+impl<I> ZScore<I> {
+    pub(crate) fn new(iter: I, mean: f64, stddev: f64) -> Self {
+        ZScore { iter, mean, stddev }
+    }
+}
+
+impl<I: Iterator> Iterator for ZScore<I>
+where
+    I::Item: Into<f64>,
+{
+    type Item = f64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.iter.next()?.into();
+        if self.stddev == 0.0 && !value.is_nan() {
+            return Some(0.0);
+        }
+        Some((value - self.mean) / self.stddev)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+pub struct NormalizeMinMax<I> {
+    iter: I,
+    min: f64,
+    range: f64,
+}
+
+impl<I> NormalizeMinMax<I> {
+    pub(crate) fn new(iter: I, min: f64, max: f64) -> Self {
+        NormalizeMinMax {
+            iter,
+            min,
+            range: max - min,
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for NormalizeMinMax<I>
+where
+    I::Item: Into<f64>,
+{
+    type Item = f64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.iter.next()?.into();
+        if self.range == 0.0 && !value.is_nan() {
+            return Some(0.0);
+        }
+        Some((value - self.min) / self.range)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum NanPolicy<T> {
+    #[default]
+    PassThrough,
+    Replace(T),
+    Drop,
+}
+
+pub struct ClampValues<I: Iterator> {
+    iter: I,
+    lo: I::Item,
+    hi: I::Item,
+    nan_policy: NanPolicy<I::Item>,
+}
+
+impl<I: Iterator> ClampValues<I>
+where
+    I::Item: PartialOrd,
+{
+    pub(crate) fn new(iter: I, lo: I::Item, hi: I::Item, nan_policy: NanPolicy<I::Item>) -> Self {
+        assert!(lo <= hi, "clamp bounds must satisfy lo <= hi");
+        ClampValues {
+            iter,
+            lo,
+            hi,
+            nan_policy,
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for ClampValues<I>
+where
+    I::Item: PartialOrd + Clone,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let next_item = self.iter.next()?;
+            let below = next_item.partial_cmp(&self.lo);
+            let above = next_item.partial_cmp(&self.hi);
+
+            return Some(match (below, above) {
+                (None, _) | (_, None) => match &self.nan_policy {
+                    NanPolicy::PassThrough => next_item,
+                    NanPolicy::Replace(replacement) => replacement.clone(),
+                    NanPolicy::Drop => continue,
+                },
+                (Some(std::cmp::Ordering::Less), _) => self.lo.clone(),
+                (_, Some(std::cmp::Ordering::Greater)) => self.hi.clone(),
+                _ => next_item,
+            });
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Fill {
+    Value(f64),
+    Previous,
+}
+
+pub struct ReplaceNonFinite<I> {
+    iter: I,
+    fill: Fill,
+    include_infinite: bool,
+    last_valid: Option<f64>,
+}
+
+impl<I> ReplaceNonFinite<I> {
+    pub(crate) fn new(iter: I, fill: Fill, include_infinite: bool) -> Self {
+        ReplaceNonFinite {
+            iter,
+            fill,
+            include_infinite,
+            last_valid: None,
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for ReplaceNonFinite<I>
+where
+    I::Item: Into<f64>,
+{
+    type Item = f64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.iter.next()?.into();
+        let is_invalid = value.is_nan() || (self.include_infinite && value.is_infinite());
+        if !is_invalid {
+            self.last_valid = Some(value);
+            return Some(value);
+        }
+
+        Some(match self.fill {
+            Fill::Value(replacement) => replacement,
+            Fill::Previous => self.last_valid.unwrap_or(value),
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+pub trait FloatLike {
+    fn is_finite(&self) -> bool;
+    fn is_nan(&self) -> bool;
+}
+
+impl FloatLike for f32 {
+    fn is_finite(&self) -> bool {
+        f32::is_finite(*self)
+    }
+
+    fn is_nan(&self) -> bool {
+        f32::is_nan(*self)
+    }
+}
+
+impl FloatLike for f64 {
+    fn is_finite(&self) -> bool {
+        f64::is_finite(*self)
+    }
+
+    fn is_nan(&self) -> bool {
+        f64::is_nan(*self)
+    }
+}
+
+impl<T: FloatLike + ?Sized> FloatLike for &T {
+    fn is_finite(&self) -> bool {
+        (**self).is_finite()
+    }
+
+    fn is_nan(&self) -> bool {
+        (**self).is_nan()
+    }
+}
+
+pub struct FilterFinite<I> {
+    iter: I,
+}
+
+impl<I> FilterFinite<I> {
+    pub(crate) fn new(iter: I) -> Self {
+        FilterFinite { iter }
+    }
+}
+
+impl<I: Iterator> Iterator for FilterFinite<I>
+where
+    I::Item: FloatLike,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.find(|item| item.is_finite())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+/// A wrapper that orders a `PartialOrd` value totally by treating incomparable values as equal.
+///
+/// This is the same policy used by the `*_by_partial_key` methods, made available for std
+/// containers and sorting functions that require `Ord`, such as `BinaryHeap`, `BTreeMap` and
+/// `slice::sort_by_key`.
+///
+/// When values like NaN are present, the resulting order is not transitive: NaN compares equal
+/// to both `1.0` and `2.0` even though `1.0 < 2.0`. Std algorithms never cause undefined
+/// behavior in that case, but their results are unspecified and recent std sorts may panic.
+/// Filter out incomparable values first, or use the crate's own sorting methods, when exact
+/// results matter.
+///
+/// # Examples
+///
+/// ```
+/// use iter_extra::prelude::*;
+/// use std::collections::BinaryHeap;
+///
+/// let mut heap = vec![2.5, 0.5, 1.5]
+///     .into_iter()
+///     .map(OrderedPartial)
+///     .collect::<BinaryHeap<_>>();
+/// assert_eq!(heap.pop(), Some(OrderedPartial(2.5)));
+///
+/// let mut readings = vec![(3, 0.7), (1, 0.2), (2, 0.5)];
+/// readings.sort_by_key(|&(_, value)| OrderedPartial(value));
+/// assert_eq!(readings, vec![(1, 0.2), (2, 0.5), (3, 0.7)]);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OrderedPartial<T>(pub T);
+
+impl<T> OrderedPartial<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: PartialOrd> PartialEq for OrderedPartial<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl<T: PartialOrd> Eq for OrderedPartial<T> {}
+
+impl<T: PartialOrd> PartialOrd for OrderedPartial<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: PartialOrd> Ord for OrderedPartial<T> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0
+            .partial_cmp(&other.0)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    }
+}
+
+pub trait IntegerLike: Copy {
+    /// The signed integer type of the same width.
+    type Signed: Copy;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn wrapping_add(self, rhs: Self) -> Self;
+    fn wrapping_sub(self, rhs: Self) -> Self;
+    fn saturating_add(self, rhs: Self) -> Self;
+    fn to_signed(self) -> Self::Signed;
+    fn from_signed(value: Self::Signed) -> Self;
+}
+
+macro_rules! impl_integer_like {
+    ($($t:ty => $signed:ty),*) => {$(
+        impl IntegerLike for $t {
+            type Signed = $signed;
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+
+            fn wrapping_add(self, rhs: Self) -> Self {
+                <$t>::wrapping_add(self, rhs)
+            }
+
+            fn wrapping_sub(self, rhs: Self) -> Self {
+                <$t>::wrapping_sub(self, rhs)
+            }
+
+            fn saturating_add(self, rhs: Self) -> Self {
+                <$t>::saturating_add(self, rhs)
+            }
+
+            fn to_signed(self) -> Self::Signed {
+                self as $signed
+            }
+
+            fn from_signed(value: Self::Signed) -> Self {
+                value as $t
+            }
+        }
+    )*};
+}
+
+impl_integer_like!(
+    i8 => i8, i16 => i16, i32 => i32, i64 => i64, i128 => i128, isize => isize,
+    u8 => i8, u16 => i16, u32 => i32, u64 => i64, u128 => i128, usize => isize
+);
+
+pub struct Cumsum<I: Iterator> {
+    iter: I,
+    total: Option<I::Item>,
+    add: fn(I::Item, I::Item) -> I::Item,
+}
+
+impl<I: Iterator> Cumsum<I> {
+    pub(crate) fn new(iter: I, add: fn(I::Item, I::Item) -> I::Item) -> Self {
+        Cumsum {
+            iter,
+            total: None,
+            add,
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for Cumsum<I>
+where
+    I::Item: Clone,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.iter.next()?;
+        let total = match self.total.take() {
+            Some(previous) => (self.add)(previous, value),
+            None => value,
+        };
+        self.total = Some(total.clone());
+        Some(total)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+pub struct CumsumChecked<I: Iterator> {
+    iter: I,
+    total: Option<I::Item>,
+    overflowed: bool,
+}
+
+impl<I: Iterator> CumsumChecked<I> {
+    pub(crate) fn new(iter: I) -> Self {
+        CumsumChecked {
+            iter,
+            total: None,
+            overflowed: false,
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for CumsumChecked<I>
+where
+    I::Item: IntegerLike,
+{
+    type Item = Option<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.iter.next()?;
+        if self.overflowed {
+            return Some(None);
+        }
+
+        let total = match self.total {
+            Some(previous) => previous.checked_add(value),
+            None => Some(value),
+        };
+        self.total = total;
+        self.overflowed = total.is_none();
+        Some(total)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+pub struct CumExtremeByPartialKey<I: Iterator, K, F> {
+    iter: I,
+    key_fn: F,
+    current: Option<(I::Item, K)>,
+    wanted: std::cmp::Ordering,
+}
+
+impl<I: Iterator, K, F> CumExtremeByPartialKey<I, K, F> {
+    pub(crate) fn new(iter: I, key_fn: F, wanted: std::cmp::Ordering) -> Self {
+        CumExtremeByPartialKey {
+            iter,
+            key_fn,
+            current: None,
+            wanted,
+        }
+    }
+}
+
+impl<I: Iterator, K, F> Iterator for CumExtremeByPartialKey<I, K, F>
+where
+    I::Item: Clone,
+    K: PartialOrd,
+    F: FnMut(&I::Item) -> K,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+        let key = (self.key_fn)(&item);
+        let replace = match &self.current {
+            None => true,
+            // A key that is not even comparable with itself (like NaN) never holds the lead.
+            Some((_, current)) => {
+                current.partial_cmp(current).is_none()
+                    || key.partial_cmp(current) == Some(self.wanted)
+            }
+        };
+        if replace {
+            self.current = Some((item, key));
+        }
+        self.current.as_ref().map(|(item, _)| item.clone())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+pub struct Diffs<I: Iterator> {
+    iter: I,
+    previous: Option<I::Item>,
+}
+
+impl<I: Iterator> Diffs<I> {
+    pub(crate) fn new(iter: I, previous: Option<I::Item>) -> Self {
+        Diffs { iter, previous }
+    }
+}
+
+impl<I: Iterator> Iterator for Diffs<I>
+where
+    I::Item: std::ops::Sub<Output = I::Item> + Clone,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.previous.is_none() {
+            self.previous = Some(self.iter.next()?);
+        }
+        let value = self.iter.next()?;
+        let previous = self.previous.replace(value.clone())?;
+        Some(value - previous)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        if self.previous.is_some() {
+            (lower, upper)
+        } else {
+            (lower.saturating_sub(1), upper.map(|n| n.saturating_sub(1)))
+        }
+    }
+}
+
+pub struct DeltaEncode<I: Iterator> {
+    iter: I,
+    previous: Option<I::Item>,
+}
+
+impl<I: Iterator> DeltaEncode<I> {
+    pub(crate) fn new(iter: I) -> Self {
+        DeltaEncode {
+            iter,
+            previous: None,
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for DeltaEncode<I>
+where
+    I::Item: IntegerLike,
+{
+    type Item = <I::Item as IntegerLike>::Signed;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.iter.next()?;
+        let delta = match self.previous.replace(value) {
+            Some(previous) => value.wrapping_sub(previous),
+            None => value,
+        };
+        Some(delta.to_signed())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+pub struct DeltaDecode<I, T> {
+    iter: I,
+    previous: Option<T>,
+}
+
+impl<I, T> DeltaDecode<I, T> {
+    pub(crate) fn new(iter: I) -> Self {
+        DeltaDecode {
+            iter,
+            previous: None,
+        }
+    }
+}
+
+impl<I: Iterator, T> Iterator for DeltaDecode<I, T>
+where
+    T: IntegerLike<Signed = I::Item>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let delta = T::from_signed(self.iter.next()?);
+        let value = match self.previous {
+            Some(previous) => previous.wrapping_add(delta),
+            None => delta,
+        };
+        self.previous = Some(value);
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+pub struct Derivative<I> {
+    iter: I,
+    previous: Option<(f64, f64)>,
+}
+
+impl<I> Derivative<I> {
+    pub(crate) fn new(iter: I) -> Self {
+        Derivative {
+            iter,
+            previous: None,
+        }
+    }
+}
+
+impl<I: Iterator<Item = (X, Y)>, X: Into<f64>, Y: Into<f64>> Iterator for Derivative<I> {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut next_point = || self.iter.next().map(|(x, y)| (x.into(), y.into()));
+        let (x0, y0) = match self.previous {
+            Some(point) => point,
+            None => next_point()?,
+        };
+        let (x1, y1) = next_point()?;
+        self.previous = Some((x1, y1));
+        Some((y1 - y0) / (x1 - x0))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        if self.previous.is_some() {
+            (lower, upper)
+        } else {
+            (lower.saturating_sub(1), upper.map(|n| n.saturating_sub(1)))
+        }
+    }
+}
+
+pub struct CumulativeTrapezoid<I> {
+    iter: I,
+    previous: Option<(f64, f64)>,
+    area: crate::stats::NeumaierSum,
+}
+
+impl<I> CumulativeTrapezoid<I> {
+    pub(crate) fn new(iter: I) -> Self {
+        CumulativeTrapezoid {
+            iter,
+            previous: None,
+            area: crate::stats::NeumaierSum::default(),
+        }
+    }
+}
+
+impl<I: Iterator<Item = (X, Y)>, X: Into<f64>, Y: Into<f64>> Iterator for CumulativeTrapezoid<I> {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (x1, y1) = self.iter.next().map(|(x, y)| (x.into(), y.into()))?;
+        if let Some((x0, y0)) = self.previous {
+            self.area.push((x1 - x0) * (y0 + y1) / 2.0);
+        }
+        self.previous = Some((x1, y1));
+        Some(self.area.total())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Aggregate {
+    #[default]
+    Mean,
+    Min,
+    Max,
+    First,
+    Last,
+}
+
+pub struct Downsample<I: Iterator, F> {
+    iter: I,
+    size: usize,
+    aggregate_fn: F,
+    group: Vec<I::Item>,
+}
+
+impl<I: Iterator, F> Downsample<I, F> {
+    pub(crate) fn new(iter: I, size: usize, aggregate_fn: F) -> Self {
+        assert!(size > 0, "group size must be greater than zero");
+        Downsample {
+            iter,
+            size,
+            aggregate_fn,
+            group: Vec::with_capacity(size),
+        }
+    }
+}
+
+impl<I: Iterator, B, F> Iterator for Downsample<I, F>
+where
+    F: FnMut(&[I::Item]) -> B,
+{
+    type Item = B;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.group.clear();
+        self.group.extend(self.iter.by_ref().take(self.size));
+        if self.group.is_empty() {
+            return None;
+        }
+        Some((self.aggregate_fn)(&self.group))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (
+            lower.div_ceil(self.size),
+            upper.map(|n| n.div_ceil(self.size)),
+        )
+    }
+}
+
+fn aggregate_group<T: Clone + Into<f64>>(aggregate: Aggregate, group: &[T]) -> f64 {
+    let mut values = group.iter().map(|item| item.clone().into());
+    match aggregate {
+        Aggregate::Mean => values.sum::<f64>() / group.len() as f64,
+        Aggregate::Min => values.fold(f64::NAN, f64::min),
+        Aggregate::Max => values.fold(f64::NAN, f64::max),
+        Aggregate::First => values.next().unwrap_or(f64::NAN),
+        Aggregate::Last => values.last().unwrap_or(f64::NAN),
+    }
+}
+
+/// Buffers runs of missing values until the next known point, then fills them linearly.
+#[derive(Default)]
+struct LinearGaps {
+    last_known: Option<(f64, f64)>,
+    missing: Vec<f64>,
+    ready: std::collections::VecDeque<(f64, Option<f64>)>,
+}
+
+impl LinearGaps {
+    fn push(&mut self, x: f64, y: Option<f64>) {
+        let Some(y1) = y else {
+            // Before the first known point a gap can never be filled, so don't hold it back.
+            if self.last_known.is_some() {
+                self.missing.push(x);
+            } else {
+                self.ready.push_back((x, None));
+            }
+            return;
+        };
+        if let Some((x0, y0)) = self.last_known {
+            for missing_x in self.missing.drain(..) {
+                let filled = y0 + (y1 - y0) * (missing_x - x0) / (x - x0);
+                self.ready.push_back((missing_x, Some(filled)));
+            }
+        }
+        self.ready.push_back((x, Some(y1)));
+        self.last_known = Some((x, y1));
+    }
+
+    fn finish(&mut self) {
+        let trailing = self.missing.drain(..).map(|x| (x, None));
+        self.ready.extend(trailing);
+    }
+
+    fn size_hint(&self, (lower, upper): (usize, Option<usize>)) -> (usize, Option<usize>) {
+        let buffered = self.missing.len() + self.ready.len();
+        (
+            lower.saturating_add(buffered),
+            upper.and_then(|n| n.checked_add(buffered)),
+        )
+    }
+}
+
+pub struct InterpolateLinear<I> {
+    iter: std::iter::Enumerate<I>,
+    /// Index and value of the last known point that was yielded.
+    last_known: Option<(usize, f64)>,
+    /// Number of missing values read since `last_known` and not yet yielded.
+    missing: usize,
+    /// The known point that ends the current gap, yielded once the gap is filled.
+    next_known: Option<(usize, f64)>,
+    exhausted: bool,
+}
+
+impl<I: Iterator> InterpolateLinear<I> {
+    pub(crate) fn new(iter: I) -> Self {
+        InterpolateLinear {
+            iter: iter.enumerate(),
+            last_known: None,
+            missing: 0,
+            next_known: None,
+            exhausted: false,
+        }
+    }
+}
+
+impl<I: Iterator<Item = Option<T>>, T: Into<f64>> Iterator for InterpolateLinear<I> {
+    type Item = Option<f64>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.missing > 0 && (self.next_known.is_some() || self.exhausted) {
+                let filled = self
+                    .last_known
+                    .zip(self.next_known)
+                    .map(|((x0, y0), (x1, y1))| {
+                        let x = x1 - self.missing;
+                        y0 + (y1 - y0) * (x - x0) as f64 / (x1 - x0) as f64
+                    });
+                self.missing -= 1;
+                return Some(filled);
+            }
+            if let Some(known) = self.next_known.take() {
+                self.last_known = Some(known);
+                return Some(Some(known.1));
+            }
+            if self.exhausted {
+                return None;
+            }
+            match self.iter.next() {
+                Some((index, Some(y))) => self.next_known = Some((index, y.into())),
+                // Before the first known point a gap can never be filled.
+                Some((_, None)) if self.last_known.is_none() => return Some(None),
+                Some((_, None)) => self.missing += 1,
+                None => self.exhausted = true,
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let buffered = self.missing + usize::from(self.next_known.is_some());
+        let (lower, upper) = self.iter.size_hint();
+        (
+            lower.saturating_add(buffered),
+            upper.and_then(|n| n.checked_add(buffered)),
+        )
+    }
+}
+
+pub struct InterpolateLinearXy<I> {
+    iter: I,
+    gaps: LinearGaps,
+}
+
+impl<I> InterpolateLinearXy<I> {
+    pub(crate) fn new(iter: I) -> Self {
+        InterpolateLinearXy {
+            iter,
+            gaps: LinearGaps::default(),
+        }
+    }
+}
+
+impl<I: Iterator<Item = (X, Option<Y>)>, X: Into<f64>, Y: Into<f64>> Iterator
+    for InterpolateLinearXy<I>
+{
+    type Item = (f64, Option<f64>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(point) = self.gaps.ready.pop_front() {
+                return Some(point);
+            }
+            match self.iter.next() {
+                Some((x, y)) => self.gaps.push(x.into(), y.map(Into::into)),
+                None if self.gaps.missing.is_empty() => return None,
+                None => self.gaps.finish(),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.gaps.size_hint(self.iter.size_hint())
+    }
+}
+
+pub struct ForwardFill<I, T> {
+    iter: I,
+    last: Option<T>,
+}
+
+impl<I, T> ForwardFill<I, T> {
+    pub(crate) fn new(iter: I) -> Self {
+        ForwardFill { iter, last: None }
+    }
+}
+
+impl<I: Iterator<Item = Option<T>>, T: Clone> Iterator for ForwardFill<I, T> {
+    type Item = Option<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(value) = self.iter.next()? {
+            self.last = Some(value);
+        }
+        Some(self.last.clone())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+pub struct BackwardFill<I, T> {
+    iter: I,
+    pending: usize,
+    fill: Option<T>,
+}
+
+impl<I, T> BackwardFill<I, T> {
+    pub(crate) fn new(iter: I) -> Self {
+        BackwardFill {
+            iter,
+            pending: 0,
+            fill: None,
+        }
+    }
+}
+
+impl<I: Iterator<Item = Option<T>>, T: Clone> Iterator for BackwardFill<I, T> {
+    type Item = Option<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pending == 0 {
+            // Count the run of missing values up to the next known one, which fills them all.
+            loop {
+                match self.iter.next() {
+                    Some(Some(value)) => {
+                        self.pending += 1;
+                        self.fill = Some(value);
+                        break;
+                    }
+                    Some(None) => self.pending += 1,
+                    None => {
+                        self.fill = None;
+                        break;
+                    }
+                }
+            }
+            if self.pending == 0 {
+                return None;
+            }
+        }
+
+        self.pending -= 1;
+        if self.pending == 0 {
+            Some(self.fill.take())
+        } else {
+            Some(self.fill.clone())
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (
+            lower.saturating_add(self.pending),
+            upper.and_then(|n| n.checked_add(self.pending)),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PeakOptions {
+    /// Minimum height of a peak above the higher of its two surrounding bases.
+    pub min_prominence: f64,
+    /// Minimum number of positions between two reported peaks.
+    pub min_distance: usize,
+}
+
+impl Default for PeakOptions {
+    fn default() -> Self {
+        PeakOptions {
+            min_prominence: 0.0,
+            min_distance: 1,
+        }
+    }
+}
+
+/// Finds local maxima in `values`, reporting the first position of each plateau.
+fn find_peaks(values: &[f64], options: PeakOptions) -> Vec<usize> {
+    let mut peaks = Vec::new();
+    let mut index = 1;
+    while index + 1 < values.len() {
+        let plateau_end = (index..values.len())
+            .find(|&i| values[i] != values[index])
+            .unwrap_or(values.len());
+        if values[index - 1] < values[index]
+            && plateau_end < values.len()
+            && values[plateau_end] < values[index]
+        {
+            peaks.push(index);
+        }
+        index = plateau_end.max(index + 1);
+    }
+
+    let prominence = |peak: usize| {
+        let height = values[peak];
+        let left_base = (values[..peak].iter().rev())
+            .take_while(|&&v| v.partial_cmp(&height) != Some(std::cmp::Ordering::Greater))
+            .fold(height, |base, &v| base.min(v));
+        let right_base = (values[peak + 1..].iter())
+            .take_while(|&&v| v.partial_cmp(&height) != Some(std::cmp::Ordering::Greater))
+            .fold(height, |base, &v| base.min(v));
+        height - left_base.max(right_base)
+    };
+    peaks.retain(|&peak| prominence(peak) >= options.min_prominence);
+
+    if options.min_distance > 1 {
+        let heights = peaks.iter().map(|&peak| -values[peak]).collect::<Vec<_>>();
+        let mut keep = vec![true; peaks.len()];
+        for &candidate in &sort_indices_by_partial_key(&heights) {
+            if !keep[candidate] {
+                continue;
+            }
+            for (other, kept) in keep.iter_mut().enumerate() {
+                if other != candidate
+                    && peaks[other].abs_diff(peaks[candidate]) < options.min_distance
+                {
+                    *kept = false;
+                }
+            }
+        }
+        let mut kept = keep.into_iter();
+        peaks.retain(|_| kept.next().unwrap_or(false));
+    }
+    peaks
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Trend {
+    Rising,
+    Falling,
+    Flat,
+}
+
+pub struct MonotoneRunsByPartialKey<I: Iterator, K, F> {
+    iter: I,
+    key_fn: F,
+    pending: Option<(I::Item, K)>,
+}
+
+impl<I: Iterator, K, F> MonotoneRunsByPartialKey<I, K, F> {
+    pub(crate) fn new(iter: I, key_fn: F) -> Self {
+        MonotoneRunsByPartialKey {
+            iter,
+            key_fn,
+            pending: None,
+        }
+    }
+}
+
+impl<I: Iterator, K, F> Iterator for MonotoneRunsByPartialKey<I, K, F>
+where
+    K: PartialOrd,
+    F: FnMut(&I::Item) -> K,
+{
+    type Item = (Trend, Vec<I::Item>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (first, mut last_key) = match self.pending.take() {
+            Some(pending) => pending,
+            None => {
+                let item = self.iter.next()?;
+                let key = (self.key_fn)(&item);
+                (item, key)
+            }
+        };
+
+        let mut run = vec![first];
+        let mut trend = Trend::Flat;
+        for item in self.iter.by_ref() {
+            let key = (self.key_fn)(&item);
+            trend = match (trend, key.partial_cmp(&last_key)) {
+                (trend, Some(std::cmp::Ordering::Equal)) => trend,
+                (Trend::Flat | Trend::Rising, Some(std::cmp::Ordering::Greater)) => Trend::Rising,
+                (Trend::Flat | Trend::Falling, Some(std::cmp::Ordering::Less)) => Trend::Falling,
+                _ => {
+                    self.pending = Some((item, key));
+                    break;
+                }
+            };
+            run.push(item);
+            last_key = key;
+        }
+        Some((trend, run))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let pending = usize::from(self.pending.is_some());
+        (
+            usize::from(lower.saturating_add(pending) > 0),
+            upper.and_then(|n| n.checked_add(pending)),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Crossing {
+    Upward,
+    Downward,
+}
+
+pub struct Crossings<I> {
+    iter: std::iter::Enumerate<I>,
+    threshold: f64,
+    hysteresis: f64,
+    above: Option<bool>,
+}
+
+impl<I: Iterator> Crossings<I> {
+    pub(crate) fn new(iter: I, threshold: f64, hysteresis: f64) -> Self {
+        assert!(hysteresis >= 0.0, "hysteresis must be non-negative");
+        Crossings {
+            iter: iter.enumerate(),
+            threshold,
+            hysteresis,
+            above: None,
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for Crossings<I>
+where
+    I::Item: Into<f64>,
+{
+    type Item = (usize, Crossing);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (index, item) in self.iter.by_ref() {
+            let value = item.into();
+            if value.is_nan() {
+                continue;
+            }
+            match self.above {
+                None => self.above = Some(value >= self.threshold),
+                Some(false) if value >= self.threshold + self.hysteresis => {
+                    self.above = Some(true);
+                    return Some((index, Crossing::Upward));
+                }
+                Some(true) if value < self.threshold - self.hysteresis => {
+                    self.above = Some(false);
+                    return Some((index, Crossing::Downward));
+                }
+                Some(_) => {}
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+pub struct BucketBy<I: Iterator, K, F> {
+    iter: I,
+    key_fn: F,
+    width: K,
+    pending: Option<(K, I::Item)>,
+}
+
+impl<I: Iterator, K, F> BucketBy<I, K, F>
+where
+    K: Copy + PartialOrd + Default,
+{
+    pub(crate) fn new(iter: I, key_fn: F, width: K) -> Self {
+        assert!(width > K::default(), "bucket width must be positive");
+        BucketBy {
+            iter,
+            key_fn,
+            width,
+            pending: None,
+        }
+    }
+}
+
+impl<I: Iterator, K, F> BucketBy<I, K, F>
+where
+    K: Copy
+        + PartialOrd
+        + Default
+        + std::ops::Add<Output = K>
+        + std::ops::Sub<Output = K>
+        + std::ops::Rem<Output = K>,
+    F: FnMut(&I::Item) -> K,
+{
+    fn keyed(&mut self, item: I::Item) -> (K, I::Item) {
+        let key = (self.key_fn)(&item);
+        let offset = key % self.width;
+        let offset = if offset < K::default() {
+            offset + self.width
+        } else {
+            offset
+        };
+        (key - offset, item)
+    }
+}
+
+impl<I: Iterator, K, F> Iterator for BucketBy<I, K, F>
+where
+    K: Copy
+        + PartialOrd
+        + Default
+        + std::ops::Add<Output = K>
+        + std::ops::Sub<Output = K>
+        + std::ops::Rem<Output = K>,
+    F: FnMut(&I::Item) -> K,
+{
+    type Item = (K, Vec<I::Item>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (start, first) = match self.pending.take() {
+            Some(pending) => pending,
+            None => {
+                let item = self.iter.next()?;
+                self.keyed(item)
+            }
+        };
+
+        let mut bucket = vec![first];
+        while let Some(item) = self.iter.next() {
+            let (item_start, item) = self.keyed(item);
+            if item_start != start {
+                self.pending = Some((item_start, item));
+                break;
+            }
+            bucket.push(item);
+        }
+        Some((start, bucket))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let pending = usize::from(self.pending.is_some());
+        (
+            usize::from(lower.saturating_add(pending) > 0),
+            upper.and_then(|n| n.checked_add(pending)),
+        )
+    }
+}
+
+pub trait IsEmpty {
+    fn is_empty(&self) -> bool;
+}
+
+impl<T> IsEmpty for Vec<T> {
+    fn is_empty(&self) -> bool {
+        Vec::is_empty(self)
+    }
+}
+
+impl<T> IsEmpty for std::collections::VecDeque<T> {
+    fn is_empty(&self) -> bool {
+        std::collections::VecDeque::is_empty(self)
+    }
+}
+
+impl<T> IsEmpty for std::collections::LinkedList<T> {
+    fn is_empty(&self) -> bool {
+        std::collections::LinkedList::is_empty(self)
+    }
+}
+
+impl<T> IsEmpty for std::collections::BinaryHeap<T> {
+    fn is_empty(&self) -> bool {
+        std::collections::BinaryHeap::is_empty(self)
+    }
+}
+
+impl<T, S> IsEmpty for std::collections::HashSet<T, S> {
+    fn is_empty(&self) -> bool {
+        std::collections::HashSet::is_empty(self)
+    }
+}
+
+impl<K, V, S> IsEmpty for std::collections::HashMap<K, V, S> {
+    fn is_empty(&self) -> bool {
+        std::collections::HashMap::is_empty(self)
+    }
+}
+
+impl<T> IsEmpty for std::collections::BTreeSet<T> {
+    fn is_empty(&self) -> bool {
+        std::collections::BTreeSet::is_empty(self)
+    }
+}
+
+impl<K, V> IsEmpty for std::collections::BTreeMap<K, V> {
+    fn is_empty(&self) -> bool {
+        std::collections::BTreeMap::is_empty(self)
+    }
+}
+
+impl IsEmpty for String {
+    fn is_empty(&self) -> bool {
+        String::is_empty(self)
+    }
+}
+
+/// A `Vec` that is guaranteed to hold at least one element.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NonEmpty<T> {
+    items: Vec<T>,
+}
+
+impl<T> NonEmpty<T> {
+    pub fn new(first: T) -> Self {
+        NonEmpty { items: vec![first] }
+    }
+
+    /// Returns `None` if `items` is empty.
+    pub fn from_vec(items: Vec<T>) -> Option<Self> {
+        (!items.is_empty()).then_some(NonEmpty { items })
+    }
+
+    pub fn first(&self) -> &T {
+        &self.items[0]
+    }
+
+    pub fn last(&self) -> &T {
+        &self.items[self.items.len() - 1]
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Always `false`; provided for parity with other collections.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    pub fn push(&mut self, item: T) {
+        self.items.push(item);
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.items
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.items.iter()
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.items
+    }
+}
+
+impl<T> From<NonEmpty<T>> for Vec<T> {
+    fn from(non_empty: NonEmpty<T>) -> Self {
+        non_empty.items
+    }
+}
+
+impl<T> IntoIterator for NonEmpty<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a NonEmpty<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollectArrayError {
+    /// The iterator ended after `actual` elements.
+    TooFew { expected: usize, actual: usize },
+    /// The iterator still had elements after the first `expected`. Iteration stops at the
+    /// first extra element, so `at_least` is always `expected + 1`, as in `LengthError`.
+    TooMany { expected: usize, at_least: usize },
+}
+
+impl std::fmt::Display for CollectArrayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CollectArrayError::TooFew { expected, actual } => {
+                write!(
+                    f,
+                    "expected {expected} elements but the iterator yielded only {actual}"
+                )
+            }
+            CollectArrayError::TooMany { expected, at_least } => {
+                write!(
+                    f,
+                    "expected {expected} elements but the iterator yielded at least {at_least}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for CollectArrayError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LengthError {
+    pub expected: usize,
+    /// Number of elements received. Iteration stops at the first extra element, so a
+    /// too-long iterator reports `expected + 1`.
+    pub actual: usize,
+}
+
+impl std::fmt::Display for LengthError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.actual > self.expected {
+            write!(f, "expected {} elements but got more", self.expected)
+        } else {
+            write!(
+                f,
+                "expected {} elements but got {}",
+                self.expected, self.actual
+            )
+        }
+    }
+}
+
+impl std::error::Error for LengthError {}
+
+#[cfg(feature = "smallvec")]
+impl<A: smallvec::Array> IsEmpty for smallvec::SmallVec<A> {
+    fn is_empty(&self) -> bool {
+        smallvec::SmallVec::is_empty(self)
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<T, const N: usize> IsEmpty for arrayvec::ArrayVec<T, N> {
+    fn is_empty(&self) -> bool {
+        arrayvec::ArrayVec::is_empty(self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DuplicateKeyPolicy {
+    /// Keep the value that was seen first.
+    KeepFirst,
+    /// Replace the stored value with each later one.
+    KeepLast,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DuplicateKeyError<K> {
+    pub key: K,
+    /// Position of the pair that repeated the key.
+    pub index: usize,
+}
+
+impl<K: std::fmt::Debug> std::fmt::Display for DuplicateKeyError<K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "duplicate key {:?} at position {}", self.key, self.index)
+    }
+}
+
+impl<K: std::fmt::Debug> std::error::Error for DuplicateKeyError<K> {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Either<L, R> {
+    Left(L),
+    Right(R),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct SplitOptions {
+    /// End each segment with the delimiter that closed it instead of dropping it.
+    pub keep_delimiters: bool,
+    /// Skip segments that contain no elements at all.
+    pub skip_empty: bool,
+}
+
+pub struct SplitOn<I, F> {
+    iter: I,
+    is_delimiter: F,
+    options: SplitOptions,
+    finished: bool,
+}
+
+impl<I, F> SplitOn<I, F> {
+    pub(crate) fn new(iter: I, is_delimiter: F, options: SplitOptions) -> Self {
+        SplitOn {
+            iter,
+            is_delimiter,
+            options,
+            finished: false,
+        }
+    }
+}
+
+impl<I: Iterator, F> Iterator for SplitOn<I, F>
+where
+    F: FnMut(&I::Item) -> bool,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.finished {
+            let mut segment = Vec::new();
+            let mut closed = false;
+            for item in self.iter.by_ref() {
+                if (self.is_delimiter)(&item) {
+                    if self.options.keep_delimiters {
+                        segment.push(item);
+                    }
+                    closed = true;
+                    break;
+                }
+                segment.push(item);
+            }
+            self.finished = !closed;
+
+            // Like `slice::split_inclusive`, there is no empty segment after the last delimiter.
+            let trailing_inclusive = self.finished && self.options.keep_delimiters;
+            if segment.is_empty() && (self.options.skip_empty || trailing_inclusive) {
+                continue;
+            }
+            return Some(segment);
+        }
+        None
+    }
+}
+
+pub struct FilterOk<I, F> {
+    iter: I,
+    predicate: F,
+}
+
+impl<I, F> FilterOk<I, F> {
+    pub(crate) fn new(iter: I, predicate: F) -> Self {
+        FilterOk { iter, predicate }
+    }
+}
+
+impl<I: Iterator<Item = Result<T, E>>, T, E, F> Iterator for FilterOk<I, F>
+where
+    F: FnMut(&T) -> bool,
+{
+    type Item = Result<T, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let predicate = &mut self.predicate;
+        self.iter.find(|result| match result {
+            Ok(value) => predicate(value),
+            Err(_) => true,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+pub struct MapOk<I, F> {
+    iter: I,
+    map_fn: F,
+}
+
+impl<I, F> MapOk<I, F> {
+    pub(crate) fn new(iter: I, map_fn: F) -> Self {
+        MapOk { iter, map_fn }
+    }
+}
+
+impl<I: Iterator<Item = Result<T, E>>, T, E, U, F> Iterator for MapOk<I, F>
+where
+    F: FnMut(T) -> U,
+{
+    type Item = Result<U, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|result| result.map(&mut self.map_fn))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+pub struct MapErrItems<I, F> {
+    iter: I,
+    map_fn: F,
+}
+
+impl<I, F> MapErrItems<I, F> {
+    pub(crate) fn new(iter: I, map_fn: F) -> Self {
+        MapErrItems { iter, map_fn }
+    }
+}
+
+impl<I: Iterator<Item = Result<T, E>>, T, E, E2, F> Iterator for MapErrItems<I, F>
+where
+    F: FnMut(E) -> E2,
+{
+    type Item = Result<T, E2>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next()
+            .map(|result| result.map_err(&mut self.map_fn))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+pub struct FlattenOk<I, J> {
+    iter: I,
+    inner: Option<J>,
+}
+
+impl<I, J> FlattenOk<I, J> {
+    pub(crate) fn new(iter: I) -> Self {
+        FlattenOk { iter, inner: None }
+    }
+}
+
+impl<I: Iterator<Item = Result<C, E>>, C, E> Iterator for FlattenOk<I, C::IntoIter>
+where
+    C: IntoIterator,
+{
+    type Item = Result<C::Item, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(value) = self.inner.as_mut().and_then(Iterator::next) {
+                return Some(Ok(value));
+            }
+            self.inner = None;
+            match self.iter.next()? {
+                Ok(container) => self.inner = Some(container.into_iter()),
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let inner = self
+            .inner
+            .as_ref()
+            .map_or((0, Some(0)), Iterator::size_hint);
+        match self.iter.size_hint() {
+            (_, Some(0)) => inner,
+            _ => (inner.0, None),
+        }
+    }
+}
+
+pub struct ProcessResults<'a, I, E> {
+    iter: I,
+    error: &'a mut Option<E>,
+}
+
+impl<I: Iterator<Item = Result<T, E>>, T, E> Iterator for ProcessResults<'_, I, E> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.error.is_some() {
+            return None;
+        }
+        match self.iter.next()? {
+            Ok(value) => Some(value),
+            Err(err) => {
+                *self.error = Some(err);
+                None
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.error.is_some() {
+            (0, Some(0))
+        } else {
+            (0, self.iter.size_hint().1)
+        }
+    }
+}
+
+pub struct WhileSome<I> {
+    iter: I,
+    done: bool,
+}
+
+impl<I> WhileSome<I> {
+    pub(crate) fn new(iter: I) -> Self {
+        WhileSome { iter, done: false }
+    }
+}
+
+impl<I: Iterator<Item = Option<T>>, T> Iterator for WhileSome<I> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let value = self.iter.next().flatten();
+        self.done = value.is_none();
+        value
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            (0, Some(0))
+        } else {
+            (0, self.iter.size_hint().1)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FoldWhile<B> {
+    /// Keep folding with this accumulator.
+    Continue(B),
+    /// Stop folding and return this accumulator.
+    Done(B),
+}
+
+impl<B> FoldWhile<B> {
+    pub fn into_inner(self) -> B {
+        match self {
+            FoldWhile::Continue(acc) | FoldWhile::Done(acc) => acc,
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        matches!(self, FoldWhile::Done(_))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExactlyOneError<T> {
+    /// The iterator yielded no elements.
+    Empty,
+    /// The iterator yielded at least two elements; these are the first two.
+    MoreThanOne { first: T, second: T },
+}
+
+impl<T> std::fmt::Display for ExactlyOneError<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExactlyOneError::Empty => f.write_str("expected exactly one element but got none"),
+            ExactlyOneError::MoreThanOne { .. } => {
+                f.write_str("expected exactly one element but got more than one")
+            }
+        }
+    }
+}
+
+impl<T: std::fmt::Debug> std::error::Error for ExactlyOneError<T> {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MoreThanOne<T> {
+    pub first: T,
+    pub second: T,
+}
+
+impl<T> std::fmt::Display for MoreThanOne<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expected at most one element but got more than one")
+    }
+}
+
+impl<T: std::fmt::Debug> std::error::Error for MoreThanOne<T> {}
+
+pub struct Positions<I, F> {
+    iter: I,
+    index: usize,
+    predicate: F,
+}
+
+impl<I, F> Positions<I, F> {
+    pub(crate) fn new(iter: I, predicate: F) -> Self {
+        Positions {
+            iter,
+            index: 0,
+            predicate,
+        }
+    }
+}
+
+impl<I: Iterator, F: FnMut(&I::Item) -> bool> Iterator for Positions<I, F> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for item in self.iter.by_ref() {
+            let index = self.index;
+            self.index += 1;
+            if (self.predicate)(&item) {
+                return Some(index);
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+pub struct CycleN<I> {
+    orig: I,
+    iter: I,
+    remaining: usize,
+}
+
+impl<I: Clone> CycleN<I> {
+    pub(crate) fn new(iter: I, n: usize) -> Self {
+        CycleN {
+            orig: iter.clone(),
+            iter,
+            remaining: n,
+        }
+    }
+}
+
+impl<I: Iterator + Clone> Iterator for CycleN<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        if let Some(item) = self.iter.next() {
+            return Some(item);
+        }
+        self.remaining -= 1;
+        if self.remaining == 0 {
+            return None;
+        }
+        self.iter = self.orig.clone();
+        let item = self.iter.next();
+        if item.is_none() {
+            self.remaining = 0;
+        }
+        item
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.remaining == 0 {
+            return (0, Some(0));
+        }
+        let rest = self.remaining - 1;
+        let (lower, upper) = self.iter.size_hint();
+        let (orig_lower, orig_upper) = self.orig.size_hint();
+        let lower = lower.saturating_add(orig_lower.saturating_mul(rest));
+        let upper = upper
+            .zip(orig_upper)
+            .and_then(|(upper, orig_upper)| upper.checked_add(orig_upper.checked_mul(rest)?));
+        (lower, upper)
+    }
+}
+
+pub struct SortedByFloatKey<T> {
+    iter: std::vec::IntoIter<(f64, T)>,
+}
+
+impl<T> SortedByFloatKey<T> {
+    pub(crate) fn new(keyed: Vec<(f64, T)>) -> Self {
+        SortedByFloatKey {
+            iter: keyed.into_iter(),
+        }
+    }
+}
+
+impl<T> Iterator for SortedByFloatKey<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(_, item)| item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
 pub trait IterExtra: Iterator {
     /// Returns the element that gives the minimum value from the specified function.
     ///
-    /// This method is similar to `Iterator::min_by_key`, but works with types that implement
-    /// `PartialOrd` instead of `Ord`. When the comparison returns `None` (indicating
-    /// incomparable values like NaN), it treats them as equal.
+    /// This method is similar to `Iterator::min_by_key`, but works with types that implement
+    /// `PartialOrd` instead of `Ord`. When the comparison returns `None` (indicating
+    /// incomparable values like NaN), it treats them as equal.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A function that extracts a key from each element for comparison
+    ///
+    /// # Returns
+    ///
+    /// * `Some(item)` - The element that produces the minimum key value
+    /// * `None` - If the iterator is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let numbers = vec![3.2, 1.5, 2.8, 0.9];
+    /// let min = numbers.iter().min_by_partial_key(|&x| x);
+    /// assert_eq!(min, Some(&0.9));
+    ///
+    /// // Works with NaN values
+    /// let with_nan = vec![1.0, f64::NAN, 2.0];
+    /// let min = with_nan.iter().min_by_partial_key(|&x| x);
+    /// assert_eq!(min, Some(&1.0));
+    /// ```
+    fn min_by_partial_key<K: PartialOrd, F: FnMut(&Self::Item) -> K>(
+        self,
+        mut key: F,
+    ) -> Option<Self::Item>
+    where
+        Self: Sized,
+    {
+        self.min_by(|x, y| {
+            key(x)
+                .partial_cmp(&key(y))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+    }
+
+    /// Returns the element that gives the maximum value from the specified function.
+    ///
+    /// This method is similar to `Iterator::max_by_key`, but works with types that implement
+    /// `PartialOrd` instead of `Ord`. When the comparison returns `None` (indicating
+    /// incomparable values like NaN), it treats them as equal.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A function that extracts a key from each element for comparison
+    ///
+    /// # Returns
+    ///
+    /// * `Some(item)` - The element that produces the maximum key value
+    /// * `None` - If the iterator is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let numbers = vec![3.2, 1.5, 2.8, 0.9];
+    /// let max = numbers.iter().max_by_partial_key(|&x| x);
+    /// assert_eq!(max, Some(&3.2));
+    ///
+    /// // Works with NaN values
+    /// let with_nan = vec![1.0, f64::NAN, 2.0];
+    /// let max = with_nan.iter().max_by_partial_key(|&x| x);
+    /// assert_eq!(max, Some(&2.0));
+    /// ```
+    fn max_by_partial_key<K: PartialOrd, F: FnMut(&Self::Item) -> K>(
+        self,
+        mut key: F,
+    ) -> Option<Self::Item>
+    where
+        Self: Sized,
+    {
+        self.max_by(|x, y| {
+            key(x)
+                .partial_cmp(&key(y))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+    }
+
+    /// Returns the element that gives the minimum value from the specified function, together
+    /// with that value.
+    ///
+    /// Similar to `min_by_partial_key`, but the key function is called exactly once per element
+    /// and the winning key is returned alongside the element, so an expensive key never has
+    /// to be recomputed. If several elements are equally minimal, the first one is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A function that extracts a key from each element for comparison
+    ///
+    /// # Returns
+    ///
+    /// * `Some((item, key))` - The element that produces the minimum key value, and that key
+    /// * `None` - If the iterator is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let points: Vec<(f64, f64)> = vec![(3.0, 4.0), (1.0, 1.0), (0.0, 2.0)];
+    /// let closest = points.into_iter().min_by_partial_key_with(|(x, y)| (x * x + y * y).sqrt());
+    /// assert_eq!(closest, Some(((1.0, 1.0), 2f64.sqrt())));
+    /// ```
+    fn min_by_partial_key_with<K: PartialOrd, F: FnMut(&Self::Item) -> K>(
+        self,
+        mut key: F,
+    ) -> Option<(Self::Item, K)>
+    where
+        Self: Sized,
+    {
+        self.map(|item| {
+            let k = key(&item);
+            (item, k)
+        })
+        .reduce(|current, candidate| {
+            if candidate.1.partial_cmp(&current.1) == Some(std::cmp::Ordering::Less) {
+                candidate
+            } else {
+                current
+            }
+        })
+    }
+
+    /// Returns the element that gives the maximum value from the specified function, together
+    /// with that value.
+    ///
+    /// Similar to `max_by_partial_key`, but the key function is called exactly once per element
+    /// and the winning key is returned alongside the element. If several elements are equally
+    /// maximal, the last one is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A function that extracts a key from each element for comparison
+    ///
+    /// # Returns
+    ///
+    /// * `Some((item, key))` - The element that produces the maximum key value, and that key
+    /// * `None` - If the iterator is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let words = vec!["tree", "forest", "leaf"];
+    /// let longest = words.into_iter().max_by_partial_key_with(|w| w.len());
+    /// assert_eq!(longest, Some(("forest", 6)));
+    /// ```
+    fn max_by_partial_key_with<K: PartialOrd, F: FnMut(&Self::Item) -> K>(
+        self,
+        mut key: F,
+    ) -> Option<(Self::Item, K)>
+    where
+        Self: Sized,
+    {
+        self.map(|item| {
+            let k = key(&item);
+            (item, k)
+        })
+        .reduce(|current, candidate| {
+            if candidate.1.partial_cmp(&current.1) == Some(std::cmp::Ordering::Less) {
+                current
+            } else {
+                candidate
+            }
+        })
+    }
+
+    /// Returns the positions of every element whose key equals the minimum key.
+    ///
+    /// Ties are common with integer or rounded keys, so instead of picking a single winner this
+    /// returns all tied indices in ascending order. Keys that are incomparable with the current
+    /// minimum (like NaN) are skipped; if the very first key is incomparable with everything
+    /// else, only its position is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A function that extracts a key from each element for comparison
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<usize>` of the tied minimum positions, empty if the iterator is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let scores = vec![3, 1, 4, 1, 5];
+    /// assert_eq!(scores.iter().positions_of_min_by_partial_key(|&&x| x), vec![1, 3]);
+    /// ```
+    fn positions_of_min_by_partial_key<K: PartialOrd, F: FnMut(&Self::Item) -> K>(
+        self,
+        key: F,
+    ) -> Vec<usize>
+    where
+        Self: Sized,
+    {
+        extreme_positions(self, key, std::cmp::Ordering::Less)
+    }
+
+    /// Returns the positions of every element whose key equals the maximum key.
+    ///
+    /// The counterpart of `positions_of_min_by_partial_key`: all tied indices are returned in
+    /// ascending order and keys incomparable with the current maximum are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A function that extracts a key from each element for comparison
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<usize>` of the tied maximum positions, empty if the iterator is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let readings = vec![2.0, 7.5, f64::NAN, 7.5, 1.0];
+    /// assert_eq!(readings.iter().positions_of_max_by_partial_key(|&&x| x), vec![1, 3]);
+    /// ```
+    fn positions_of_max_by_partial_key<K: PartialOrd, F: FnMut(&Self::Item) -> K>(
+        self,
+        key: F,
+    ) -> Vec<usize>
+    where
+        Self: Sized,
+    {
+        extreme_positions(self, key, std::cmp::Ordering::Greater)
+    }
+
+    /// Finds both the minimum and maximum elements by key, with their positions, in one pass.
+    ///
+    /// The key function is called exactly once per element. Ties follow `min_by_partial_key`
+    /// and `max_by_partial_key`: the first minimal and the last maximal element win, and
+    /// incomparable keys (like NaN) are treated as equal.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A function that extracts a key from each element for comparison
+    ///
+    /// # Returns
+    ///
+    /// * `Some(((min_idx, min_item), (max_idx, max_item)))` - The extremes and their positions
+    /// * `None` - If the iterator is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let xs = vec![4.0, -1.5, 9.0, 2.0];
+    /// let extremes = xs.into_iter().minmax_with_indices_by_partial_key(|&x| x);
+    /// assert_eq!(extremes, Some(((1, -1.5), (2, 9.0))));
+    /// ```
+    #[allow(clippy::type_complexity)]
+    fn minmax_with_indices_by_partial_key<K, F>(
+        self,
+        mut key: F,
+    ) -> Option<((usize, Self::Item), (usize, Self::Item))>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        K: PartialOrd + Clone,
+        F: FnMut(&Self::Item) -> K,
+    {
+        let mut iter = self.enumerate();
+        let (index, first) = iter.next()?;
+        let first_key = key(&first);
+        let mut min = (index, first.clone(), first_key.clone());
+        let mut max = (index, first, first_key);
+
+        for (index, item) in iter {
+            let k = key(&item);
+            let is_min = k.partial_cmp(&min.2) == Some(std::cmp::Ordering::Less);
+            let is_max = k.partial_cmp(&max.2) != Some(std::cmp::Ordering::Less);
+            match (is_min, is_max) {
+                (true, true) => {
+                    min = (index, item.clone(), k.clone());
+                    max = (index, item, k);
+                }
+                (true, false) => min = (index, item, k),
+                (false, true) => max = (index, item, k),
+                (false, false) => {}
+            }
+        }
+        Some(((min.0, min.1), (max.0, max.1)))
+    }
+
+    /// Collects the elements into a `Vec`.
+    ///
+    /// Equivalent to `collect::<Vec<_>>()`, without the turbofish.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec` with all elements in iteration order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let squares = (1..=4).map(|n| n * n).collect_vec();
+    /// assert_eq!(squares, vec![1, 4, 9, 16]);
+    /// ```
+    fn collect_vec(self) -> Vec<Self::Item>
+    where
+        Self: Sized,
+    {
+        self.collect()
+    }
+
+    fn collect_some_vec(self) -> Option<Vec<Self::Item>>
+    where
+        Self: Sized,
+    {
+        self.collect_some()
+    }
+
+    /// Collects the elements into a `NonEmpty`, returning `None` if there are none.
+    ///
+    /// Unlike `collect_some_vec`, the result keeps the non-emptiness guarantee in its type, so
+    /// `first` and `last` can be called without unwrapping.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(non_empty)` - If the iterator yielded at least one element
+    /// * `None` - If the iterator was empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let scores = vec![72, 95, 88];
+    /// let collected = scores.into_iter().collect_nonempty().unwrap();
+    /// assert_eq!(*collected.first(), 72);
+    /// assert_eq!(*collected.last(), 88);
+    /// assert_eq!(collected.into_vec(), vec![72, 95, 88]);
+    ///
+    /// assert!(std::iter::empty::<i32>().collect_nonempty().is_none());
+    /// ```
+    fn collect_nonempty(self) -> Option<NonEmpty<Self::Item>>
+    where
+        Self: Sized,
+    {
+        NonEmpty::from_vec(self.collect())
+    }
+
+    /// Collects the elements into any collection, returning `None` if it ends up empty.
+    ///
+    /// The generic form of `collect_some_vec`: the target can be any `FromIterator`
+    /// collection that implements `IsEmpty`, such as `HashSet`, `BTreeMap` or `String`.
+    /// Emptiness is checked on the collected result, so a set built from duplicates or a
+    /// string built from empty pieces follows the collection's own notion of empty.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(collection)` - If the collection holds at least one element
+    /// * `None` - If the collection is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use iter_extra::IterExtra;
+    ///
+    /// let tags = vec!["rust", "iter", "rust"];
+    /// let unique: Option<HashSet<&str>> = tags.into_iter().collect_some();
+    /// assert_eq!(unique.map(|set| set.len()), Some(2));
+    ///
+    /// let name: Option<String> = vec!["", ""].into_iter().collect_some();
+    /// assert_eq!(name, None);
+    /// ```
+    fn collect_some<C>(self) -> Option<C>
+    where
+        Self: Sized,
+        C: FromIterator<Self::Item> + IsEmpty,
+    {
+        Some(self.collect::<C>()).filter(|c| !c.is_empty())
+    }
+
+    /// Collects `(key, value)` pairs into a `BTreeMap`, returning `None` if there are none.
+    ///
+    /// The sorted-map member of the `collect_some_*` family. Later values replace earlier
+    /// ones for repeated keys, as with `collect`.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(map)` - If the iterator yielded at least one pair
+    /// * `None` - If the iterator was empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let env = vec![("PATH", "/bin"), ("HOME", "/root")];
+    /// let sorted = env.into_iter().collect_some_btreemap().unwrap();
+    /// assert_eq!(sorted.keys().next(), Some(&"HOME"));
+    ///
+    /// assert!(std::iter::empty::<(u8, u8)>().collect_some_btreemap().is_none());
+    /// ```
+    fn collect_some_btreemap<K, V>(self) -> Option<std::collections::BTreeMap<K, V>>
+    where
+        Self: Sized + Iterator<Item = (K, V)>,
+        K: Ord,
+    {
+        self.collect_some()
+    }
+
+    /// Collects the elements into a `BTreeSet`, returning `None` if there are none.
+    ///
+    /// The sorted-set member of the `collect_some_*` family.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(set)` - If the iterator yielded at least one element
+    /// * `None` - If the iterator was empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let ids = vec![42, 7, 42, 19];
+    /// let unique = ids.into_iter().collect_some_btreeset().unwrap();
+    /// assert_eq!(unique.into_iter().collect::<Vec<_>>(), vec![7, 19, 42]);
+    /// ```
+    fn collect_some_btreeset(self) -> Option<std::collections::BTreeSet<Self::Item>>
+    where
+        Self: Sized,
+        Self::Item: Ord,
+    {
+        self.collect_some()
+    }
+
+    fn collect_ok_vec_or<E>(self, err: E) -> Result<Vec<Self::Item>, E>
+    where
+        Self: Sized,
+    {
+        Ok(self.collect::<Vec<Self::Item>>()).and_then(
+            |v| {
+                if v.is_empty() { Err(err) } else { Ok(v) }
+            },
+        )
+    }
+
+    /// Collects the elements into a `Vec`, or returns the error built by `err_fn` if there are
+    /// none.
+    ///
+    /// The lazy counterpart of `collect_ok_vec_or`: `err_fn` is only called when the iterator
+    /// is empty, so expensive errors are not constructed up front.
+    ///
+    /// # Arguments
+    ///
+    /// * `err_fn` - A function that produces the error for an empty iterator
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(vec)` - If the iterator yielded at least one element
+    /// * `Err(err_fn())` - If the iterator was empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let path = "config.toml";
+    /// let lines: Result<Vec<&str>, String> = ""
+    ///     .lines()
+    ///     .collect_ok_vec_or_else(|| format!("{path} has no entries"));
+    /// assert_eq!(lines, Err("config.toml has no entries".to_string()));
+    /// ```
+    fn collect_ok_vec_or_else<E, F>(self, err_fn: F) -> Result<Vec<Self::Item>, E>
+    where
+        Self: Sized,
+        F: FnOnce() -> E,
+    {
+        self.collect_some_vec().ok_or_else(err_fn)
+    }
+
+    fn collect_ok_vec_or_default<E: Default>(self) -> Result<Vec<Self::Item>, E>
+    where
+        Self: Sized,
+    {
+        Ok(self.collect::<Vec<Self::Item>>()).and_then(|v| {
+            if v.is_empty() {
+                Err(E::default())
+            } else {
+                Ok(v)
+            }
+        })
+    }
+
+    /// Returns an iterator that yields the distance from each element to its last occurrence.
+    ///
+    /// For each element in the iterator, this method returns the number of elements between
+    /// the current element and the previous occurrence of the same element. If the element
+    /// hasn't appeared before, it returns the current index.
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields `usize` values representing the delta for each element
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let items = vec!['a', 'b', 'c', 'a', 'c'];
+    /// let deltas: Vec<usize> = items.into_iter().deltas().collect();
+    /// assert_eq!(deltas, vec![0, 1, 2, 2, 1]);
+    /// ```
+    fn deltas(self) -> Deltas<Self>
+    where
+        Self: Sized,
+        Self::Item: std::cmp::PartialEq,
+    {
+        Deltas::new(self)
+    }
+
+    /// Returns an iterator that yields the distance from each element to its last occurrence,
+    /// using a custom comparison function.
+    ///
+    /// Similar to `deltas`, but uses a custom comparison function to determine element equality.
+    /// Two elements are considered equal when the comparison function returns `Ordering::Equal`.
+    ///
+    /// # Arguments
+    ///
+    /// * `cmp_fn` - A function that compares two elements and returns an `Ordering`
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields `usize` values representing the delta for each element
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let items = vec![1.1f64, 2.2, 3.3, 1.2, 2.1];
+    /// let deltas: Vec<usize> = items.into_iter()
+    ///     .deltas_by(|a, b| a.floor().total_cmp(&b.floor()))
+    ///     .collect();
+    /// assert_eq!(deltas, vec![0, 1, 2, 2, 2]);
+    /// ```
+    fn deltas_by<F>(self, cmp_fn: F) -> DeltasBy<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item, &Self::Item) -> std::cmp::Ordering,
+    {
+        DeltasBy::new(self, cmp_fn)
+    }
+
+    /// Returns an iterator that yields the distance from each element to its last occurrence,
+    /// comparing elements by a key extracted from each element.
+    ///
+    /// Similar to `deltas`, but determines element equality by comparing the keys extracted
+    /// by the provided key function. Two elements are considered equal if their keys are equal.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_fn` - A function that extracts a key from each element for comparison
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields `usize` values representing the delta for each element
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let items = vec!["apple", "banana", "apricot", "blueberry"];
+    /// let deltas: Vec<usize> = items.into_iter()
+    ///     .deltas_by_key(|s| s.chars().next())
+    ///     .collect();
+    /// assert_eq!(deltas, vec![0, 1, 1, 1]);
+    /// ```
+    fn deltas_by_key<K, F>(self, key_fn: F) -> DeltasByKey<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> K,
+        K: std::cmp::PartialEq,
+    {
+        DeltasByKey::new(self, key_fn)
+    }
+
+    /// Returns a lending iterator over overlapping windows of `size` consecutive elements.
+    ///
+    /// Unlike collecting windows of owned values, this does not require `Clone`: each window
+    /// is a borrowed slice into an internal buffer, obtained through `WindowsRef::next_window`.
+    /// Because every window borrows the adapter, it cannot implement `Iterator`.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The number of elements in each window
+    ///
+    /// # Returns
+    ///
+    /// A `WindowsRef` that yields `&[Self::Item]` views of length `size`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let mut windows = vec![1, 2, 3, 4].into_iter().windows_ref(3);
+    /// assert_eq!(windows.next_window(), Some(&[1, 2, 3][..]));
+    /// assert_eq!(windows.next_window(), Some(&[2, 3, 4][..]));
+    /// assert_eq!(windows.next_window(), None);
+    /// ```
+    fn windows_ref(self, size: usize) -> WindowsRef<Self>
+    where
+        Self: Sized,
+    {
+        WindowsRef::new(self, size)
+    }
+
+    /// Returns an iterator that repeatedly hands the underlying iterator to a closure, which
+    /// may consume as many elements as it needs to produce one output.
+    ///
+    /// The adapter yields whatever the closure returns and stops as soon as it returns `None`.
+    /// This allows custom framing logic, such as grouping elements until some accumulated size
+    /// is reached.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_fn` - A function that pulls elements from the iterator and returns one batch
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields the values produced by `batch_fn`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let sizes = vec![3, 4, 2, 5, 1, 6];
+    /// // Accumulate until the total size reaches at least 6
+    /// let batches: Vec<Vec<i32>> = sizes
+    ///     .into_iter()
+    ///     .batching(|it| {
+    ///         let mut batch = Vec::new();
+    ///         let mut total = 0;
+    ///         for size in it.by_ref() {
+    ///             batch.push(size);
+    ///             total += size;
+    ///             if total >= 6 {
+    ///                 break;
+    ///             }
+    ///         }
+    ///         (!batch.is_empty()).then_some(batch)
+    ///     })
+    ///     .collect();
+    /// assert_eq!(batches, vec![vec![3, 4], vec![2, 5], vec![1, 6]]);
+    /// ```
+    fn batching<B, F>(self, batch_fn: F) -> Batching<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(&mut Self) -> Option<B>,
+    {
+        Batching::new(self, batch_fn)
+    }
+
+    /// Returns an iterator that tags each element with its position in the sequence.
+    ///
+    /// Each element is paired with a `Position`: `First`, `Middle` or `Last`, or `Only` when
+    /// the iterator yields a single element. The adapter looks ahead by one element to detect
+    /// the end of the sequence.
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields `(Position, Self::Item)` pairs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::prelude::*;
+    ///
+    /// let tagged: Vec<(Position, char)> = "abc".chars().with_position().collect();
+    /// assert_eq!(
+    ///     tagged,
+    ///     vec![(Position::First, 'a'), (Position::Middle, 'b'), (Position::Last, 'c')]
+    /// );
+    ///
+    /// let single: Vec<(Position, i32)> = std::iter::once(1).with_position().collect();
+    /// assert_eq!(single, vec![(Position::Only, 1)]);
+    /// ```
+    fn with_position(self) -> WithPosition<Self>
+    where
+        Self: Sized,
+    {
+        WithPosition::new(self)
+    }
+
+    /// Returns an iterator that places a copy of `separator` between each pair of adjacent
+    /// elements.
+    ///
+    /// This is a stable counterpart of the unstable `Iterator::intersperse`. Because the names
+    /// collide, call it with fully qualified syntax to avoid the `unstable_name_collisions` lint.
+    ///
+    /// # Arguments
+    ///
+    /// * `separator` - The element to yield between adjacent elements
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields the original elements interleaved with separators
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let items = vec!["a", "b", "c"];
+    /// let joined: String = IterExtra::intersperse(items.into_iter(), ", ").collect();
+    /// assert_eq!(joined, "a, b, c");
+    /// ```
+    fn intersperse(self, separator: Self::Item) -> Intersperse<Self>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        Intersperse::new(self, separator)
+    }
+
+    /// Returns an iterator that places a separator produced by `separator_fn` between each
+    /// pair of adjacent elements.
+    ///
+    /// Similar to `intersperse`, but the separator is generated on demand, so it does not
+    /// need to implement `Clone`.
+    ///
+    /// # Arguments
+    ///
+    /// * `separator_fn` - A function that produces the element to yield between adjacent elements
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields the original elements interleaved with separators
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let items = vec![vec![1], vec![2], vec![3]];
+    /// let result: Vec<Vec<i32>> = IterExtra::intersperse_with(items.into_iter(), Vec::new).collect();
+    /// assert_eq!(result, vec![vec![1], vec![], vec![2], vec![], vec![3]]);
+    /// ```
+    fn intersperse_with<F>(self, separator_fn: F) -> IntersperseWith<Self, F>
+    where
+        Self: Sized,
+        F: FnMut() -> Self::Item,
+    {
+        IntersperseWith::new(self, separator_fn)
+    }
+
+    /// Concatenates all elements into a `String`, placing `separator` between adjacent elements.
+    ///
+    /// Each element is written directly into the output buffer through its `Display`
+    /// implementation, so no intermediate strings are allocated.
+    ///
+    /// # Arguments
+    ///
+    /// * `separator` - The string to insert between adjacent elements
+    ///
+    /// # Returns
+    ///
+    /// A `String` containing the formatted elements, or an empty string if the iterator is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let numbers = vec![1, 2, 3];
+    /// assert_eq!(numbers.iter().join(", "), "1, 2, 3");
+    /// ```
+    fn join(self, separator: &str) -> String
+    where
+        Self: Sized,
+        Self::Item: std::fmt::Display,
+    {
+        self.join_map(separator, |item| item)
+    }
+
+    /// Concatenates the values produced by `format_fn` into a `String`, placing `separator`
+    /// between adjacent values.
+    ///
+    /// Similar to `join`, but each element is first mapped to a `Display` value, which is
+    /// then written directly into the output buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `separator` - The string to insert between adjacent elements
+    /// * `format_fn` - A function that maps each element to a displayable value
+    ///
+    /// # Returns
+    ///
+    /// A `String` containing the formatted values, or an empty string if the iterator is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let points = vec![(1, 2), (3, 4)];
+    /// let joined = points.iter().join_map("; ", |(x, y)| format!("{x}:{y}"));
+    /// assert_eq!(joined, "1:2; 3:4");
+    /// ```
+    fn join_map<D, F>(self, separator: &str, mut format_fn: F) -> String
+    where
+        Self: Sized,
+        D: std::fmt::Display,
+        F: FnMut(Self::Item) -> D,
+    {
+        use std::fmt::Write;
+
+        let mut result = String::new();
+        for (index, item) in self.enumerate() {
+            if index > 0 {
+                result.push_str(separator);
+            }
+            write!(result, "{}", format_fn(item)).expect("writing to a String cannot fail");
+        }
+        result
+    }
+
+    /// Returns a value that lazily formats all elements, placing `separator` between adjacent
+    /// elements, when it is displayed.
+    ///
+    /// Each element is written straight into the target formatter by `format_fn`, so the
+    /// result can be embedded in `format!` or `write!` without allocating intermediate strings.
+    /// The iterator is consumed on first use, so the value can only be formatted once.
+    ///
+    /// # Arguments
+    ///
+    /// * `separator` - The string to insert between adjacent elements
+    /// * `format_fn` - A function that writes one element into the formatter
+    ///
+    /// # Returns
+    ///
+    /// A `FormatWith` value implementing `Display`
+    ///
+    /// # Panics
+    ///
+    /// Formatting the returned value more than once panics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let readings = vec![("cpu", 0.5), ("mem", 0.75)];
+    /// let line = format!(
+    ///     "[{}]",
+    ///     readings
+    ///         .iter()
+    ///         .format_with(", ", |(name, value), f| write!(f, "{name}={value}"))
+    /// );
+    /// assert_eq!(line, "[cpu=0.5, mem=0.75]");
+    /// ```
+    fn format_with<F>(self, separator: &str, format_fn: F) -> FormatWith<'_, Self, F>
+    where
+        Self: Sized,
+        F: FnMut(Self::Item, &mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+    {
+        FormatWith::new(self, separator, format_fn)
+    }
+
+    /// Zips this iterator with another one, panicking if they do not have the same length.
+    ///
+    /// Unlike `Iterator::zip`, which silently stops at the end of the shorter iterator, this
+    /// adapter checks that both iterators end together.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The iterator to zip with
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields `(Self::Item, J::Item)` pairs
+    ///
+    /// # Panics
+    ///
+    /// Panics when one iterator is exhausted before the other.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let pairs: Vec<(i32, char)> = vec![1, 2].into_iter().zip_eq(vec!['a', 'b']).collect();
+    /// assert_eq!(pairs, vec![(1, 'a'), (2, 'b')]);
+    /// ```
+    fn zip_eq<J>(self, other: J) -> ZipEq<Self, J::IntoIter>
+    where
+        Self: Sized,
+        J: IntoIterator,
+    {
+        ZipEq::new(self, other.into_iter())
+    }
+
+    /// Zips this iterator with another one, yielding an error if they do not have the same
+    /// length.
+    ///
+    /// Similar to `zip_eq`, but instead of panicking, a length mismatch is reported as a final
+    /// `Err(ZipEqError)` item describing where and which iterator ended first.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The iterator to zip with
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields `Ok((Self::Item, J::Item))` pairs, followed by one `Err` item
+    /// if the lengths differ
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let result: Result<Vec<(i32, char)>, _> =
+    ///     vec![1, 2, 3].into_iter().try_zip_eq(vec!['a', 'b']).collect();
+    /// let err = result.unwrap_err();
+    /// assert_eq!(err.index, 2);
+    /// assert!(!err.left_exhausted);
+    /// ```
+    fn try_zip_eq<J>(self, other: J) -> TryZipEq<Self, J::IntoIter>
+    where
+        Self: Sized,
+        J: IntoIterator,
+    {
+        TryZipEq::new(self, other.into_iter())
+    }
+
+    /// Returns an iterator that combines elements of this iterator and another one through
+    /// a function.
+    ///
+    /// Equivalent to `zip(other).map(|(a, b)| zip_fn(a, b))`. Like `zip`, it stops as soon as
+    /// either iterator is exhausted.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The iterator to combine with
+    /// * `zip_fn` - A function that combines one element from each iterator
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields the values returned by `zip_fn`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let a = vec![1.0, 2.0, 3.0];
+    /// let b = vec![4.0, 5.0, 6.0];
+    /// let products: Vec<f64> = a.iter().zip_with(&b, |x, y| x * y).collect();
+    /// assert_eq!(products, vec![4.0, 10.0, 18.0]);
+    /// ```
+    fn zip_with<J, R, F>(self, other: J, zip_fn: F) -> ZipWith<Self, J::IntoIter, F>
+    where
+        Self: Sized,
+        J: IntoIterator,
+        F: FnMut(Self::Item, J::Item) -> R,
+    {
+        ZipWith::new(self, other.into_iter(), zip_fn)
+    }
+
+    /// Returns an iterator that alternates between elements of this iterator and another one.
+    ///
+    /// The first element comes from this iterator. Once either iterator is exhausted, the
+    /// remaining elements of the other one are yielded in order.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The iterator to interleave with
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields the elements of both iterators, alternating between them
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let merged: Vec<i32> = vec![1, 3, 5, 7].into_iter().interleave(vec![2, 4]).collect();
+    /// assert_eq!(merged, vec![1, 2, 3, 4, 5, 7]);
+    /// ```
+    fn interleave<J>(self, other: J) -> Interleave<Self, J::IntoIter>
+    where
+        Self: Sized,
+        J: IntoIterator<Item = Self::Item>,
+    {
+        Interleave::new(self, other.into_iter())
+    }
+
+    /// Returns an iterator that alternates between elements of this iterator and another one,
+    /// stopping as soon as either iterator is exhausted.
+    ///
+    /// Similar to `interleave`, but the sequence ends the first time the iterator whose turn
+    /// it is has no more elements.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The iterator to interleave with
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields the elements of both iterators, alternating between them
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let merged: Vec<i32> = vec![1, 3, 5, 7]
+    ///     .into_iter()
+    ///     .interleave_shortest(vec![2, 4])
+    ///     .collect();
+    /// assert_eq!(merged, vec![1, 2, 3, 4, 5]);
+    /// ```
+    fn interleave_shortest<J>(self, other: J) -> InterleaveShortest<Self, J::IntoIter>
+    where
+        Self: Sized,
+        J: IntoIterator<Item = Self::Item>,
+    {
+        InterleaveShortest::new(self, other.into_iter())
+    }
+
+    /// Merges an iterator of sorted iterators into a single sorted iterator.
+    ///
+    /// The sources are merged lazily through a binary heap holding the current head of each
+    /// source, so producing each element costs `O(log k)` for `k` sources. Each source must
+    /// already be sorted in ascending order. Equal elements are yielded in source order.
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields the elements of all sources in ascending order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let sources = vec![vec![1, 4, 7], vec![2, 5], vec![3, 6, 8]];
+    /// let merged: Vec<i32> = sources.into_iter().kmerge().collect();
+    /// assert_eq!(merged, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+    /// ```
+    fn kmerge(self) -> KMerge<<Self::Item as IntoIterator>::IntoIter>
+    where
+        Self: Sized,
+        Self::Item: IntoIterator,
+        <Self::Item as IntoIterator>::Item: Ord,
+    {
+        KMerge::new(self.map(IntoIterator::into_iter))
+    }
+
+    /// Merges an iterator of sorted iterators into a single iterator sorted by the specified
+    /// key function.
+    ///
+    /// Similar to `kmerge`, but orders elements by a key that implements `PartialOrd`. When the
+    /// comparison returns `None` (indicating incomparable values like NaN), it treats them as
+    /// equal. Each source must already be sorted by the same key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_fn` - A function that extracts a key from each element for comparison
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields the elements of all sources in ascending key order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let sources = vec![vec![0.5, 2.5], vec![1.0, 1.5, 3.0]];
+    /// let merged: Vec<f64> = sources.into_iter().kmerge_by_partial_key(|&x| x).collect();
+    /// assert_eq!(merged, vec![0.5, 1.0, 1.5, 2.5, 3.0]);
+    /// ```
+    fn kmerge_by_partial_key<K, F>(
+        self,
+        key_fn: F,
+    ) -> KMergeByPartialKey<<Self::Item as IntoIterator>::IntoIter, F>
+    where
+        Self: Sized,
+        Self::Item: IntoIterator,
+        F: FnMut(&<Self::Item as IntoIterator>::Item) -> K,
+        K: PartialOrd,
+    {
+        KMergeByPartialKey::new(self.map(IntoIterator::into_iter), key_fn)
+    }
+
+    /// Returns an iterator that performs an ordered merge join of this iterator and another one.
+    ///
+    /// Both iterators must be sorted consistently with `cmp_fn`. At each step the current
+    /// heads are compared: the smaller one is yielded as `Left` or `Right`, and equal heads are
+    /// yielded together as `Both`. Neither side is materialized.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The iterator to join with
+    /// * `cmp_fn` - A function that compares an element of this iterator with one of `other`
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields `EitherOrBoth<Self::Item, J::Item>` values
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::prelude::*;
+    ///
+    /// let users = vec![(1, "alice"), (2, "bob"), (4, "dave")];
+    /// let orders = vec![(2, 100), (3, 50), (4, 75)];
+    /// let joined: Vec<_> = users
+    ///     .iter()
+    ///     .merge_join_by(orders.iter(), |(a, _), (b, _)| a.cmp(b))
+    ///     .collect();
+    /// assert_eq!(
+    ///     joined,
+    ///     vec![
+    ///         EitherOrBoth::Left(&(1, "alice")),
+    ///         EitherOrBoth::Both(&(2, "bob"), &(2, 100)),
+    ///         EitherOrBoth::Right(&(3, 50)),
+    ///         EitherOrBoth::Both(&(4, "dave"), &(4, 75)),
+    ///     ]
+    /// );
+    /// ```
+    fn merge_join_by<J, F>(self, other: J, cmp_fn: F) -> MergeJoinBy<Self, J::IntoIter, F>
+    where
+        Self: Sized,
+        J: IntoIterator,
+        F: FnMut(&Self::Item, &J::Item) -> std::cmp::Ordering,
+    {
+        MergeJoinBy::new(self, other.into_iter(), cmp_fn)
+    }
+
+    /// Returns an iterator over all combinations of elements as fixed-size tuples.
+    ///
+    /// The tuple arity (2, 3 or 4) is chosen by the target type. Combinations are yielded in
+    /// lexicographic order of element positions, each element appearing at most once per tuple.
+    /// Elements are buffered as they are first needed and cloned into each tuple, so no heap
+    /// allocation happens per combination.
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields tuples of type `T`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let pairs: Vec<(i32, i32)> = vec![1, 2, 3].into_iter().tuple_combinations().collect();
+    /// assert_eq!(pairs, vec![(1, 2), (1, 3), (2, 3)]);
+    ///
+    /// let triples: Vec<(char, char, char)> = "abcd".chars().tuple_combinations().collect();
+    /// assert_eq!(
+    ///     triples,
+    ///     vec![('a', 'b', 'c'), ('a', 'b', 'd'), ('a', 'c', 'd'), ('b', 'c', 'd')]
+    /// );
+    /// ```
+    fn tuple_combinations<T>(self) -> TupleCombinations<Self, T>
+    where
+        Self: Sized,
+        T: HomogeneousTuple<Item = Self::Item>,
+    {
+        TupleCombinations::new(self)
+    }
+
+    /// Returns an iterator over all elements sorted in ascending order of the specified key.
+    ///
+    /// The elements are collected internally together with their keys and sorted with a stable
+    /// sort, so the key is computed once per element. Keys that are not comparable with
+    /// themselves (like NaN) are placed after all other keys, in input order. Any other pair of
+    /// incomparable keys is treated as equal. Unlike `slice::sort_by`, this never panics on keys
+    /// that lack a total order.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_fn` - A function that extracts a key from each element for comparison
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields the elements in ascending key order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let numbers = vec![3.2, 1.5, 2.8, 0.9];
+    /// let sorted: Vec<f64> = numbers.into_iter().sorted_by_partial_key(|&x| x).collect();
+    /// assert_eq!(sorted, vec![0.9, 1.5, 2.8, 3.2]);
+    ///
+    /// // NaN keys go last
+    /// let with_nan = vec![2.0, f64::NAN, 1.0];
+    /// let sorted: Vec<f64> = with_nan.into_iter().sorted_by_partial_key(|&x| x).collect();
+    /// assert_eq!(sorted[..2], [1.0, 2.0]);
+    /// assert!(sorted[2].is_nan());
+    /// ```
+    fn sorted_by_partial_key<K, F>(self, mut key_fn: F) -> SortedByPartialKey<K, Self::Item>
+    where
+        Self: Sized,
+        K: PartialOrd,
+        F: FnMut(&Self::Item) -> K,
+    {
+        let mut keyed = self.map(|item| (key_fn(&item), item)).collect::<Vec<_>>();
+        let indices = stable_sort_indices(keyed.len(), |a, b| {
+            partial_key_less(&keyed[a].0, &keyed[b].0)
+        });
+        permute_in_place(&mut keyed, indices);
+        SortedByPartialKey::new(keyed)
+    }
+
+    /// Returns the positions of the elements in the order that would sort them by key.
+    ///
+    /// Uses the same stable sort as `sorted_by_partial_key`, so the key is computed once per
+    /// element, equal keys keep their input order, keys that are not comparable with themselves
+    /// (like NaN) come last, and it never panics on keys that lack a total order. The resulting
+    /// permutation can be used to reorder several parallel collections consistently.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_fn` - A function that extracts a key from each element for comparison
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<usize>` where entry `i` is the original position of the `i`-th smallest element
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let ages = vec![31.5, 22.0, 45.25];
+    /// let names = vec!["ana", "bo", "cy"];
+    /// let order = ages.iter().argsort_by_partial_key(|&&x| x);
+    /// assert_eq!(order, vec![1, 0, 2]);
+    ///
+    /// let by_age: Vec<&str> = order.iter().map(|&i| names[i]).collect();
+    /// assert_eq!(by_age, vec!["bo", "ana", "cy"]);
+    /// ```
+    fn argsort_by_partial_key<K, F>(self, mut key_fn: F) -> Vec<usize>
+    where
+        Self: Sized,
+        K: PartialOrd,
+        F: FnMut(&Self::Item) -> K,
+    {
+        let keys = self.map(|item| key_fn(&item)).collect::<Vec<_>>();
+        sort_indices_by_partial_key(&keys)
+    }
+
+    /// Returns an iterator that yields elements in ascending key order, sorting incrementally.
+    ///
+    /// The elements are collected into a binary heap in `O(n)`, and each call to `next` pops
+    /// the smallest remaining element in `O(log n)`. Taking only the first `k` elements therefore
+    /// costs `O(n + k log n)` rather than a full sort. When the comparison returns `None`
+    /// (indicating incomparable values like NaN), it treats them as equal; equal keys are
+    /// yielded in input order.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_fn` - A function that extracts a key from each element for comparison
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields the elements in ascending key order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let latencies = vec![12.5, 3.1, 8.7, 1.2, 9.9, 4.4];
+    /// let fastest: Vec<f64> = latencies
+    ///     .into_iter()
+    ///     .sorted_stream_by_partial_key(|&x| x)
+    ///     .take(3)
+    ///     .collect();
+    /// assert_eq!(fastest, vec![1.2, 3.1, 4.4]);
+    /// ```
+    fn sorted_stream_by_partial_key<K, F>(
+        self,
+        key_fn: F,
+    ) -> SortedStreamByPartialKey<Self::Item, K>
+    where
+        Self: Sized,
+        K: PartialOrd,
+        F: FnMut(&Self::Item) -> K,
+    {
+        SortedStreamByPartialKey::new(self, key_fn)
+    }
+
+    /// Returns the `k` elements with the smallest keys, in ascending key order.
+    ///
+    /// This is a partial sort: a bounded heap of size `k` is maintained while the iterator is
+    /// consumed, so the total cost is `O(n log k)` and only `k` elements are kept in memory.
+    /// When the comparison returns `None` (indicating incomparable values like NaN), it treats
+    /// them as equal; among equal keys, earlier elements are preferred and come first.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The maximum number of elements to return
+    /// * `key_fn` - A function that extracts a key from each element for comparison
+    ///
+    /// # Returns
+    ///
+    /// A `Vec` of at most `k` elements, sorted by ascending key
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let scores = vec![("ann", 7.5), ("bob", 9.1), ("cid", 8.3), ("dan", 6.0)];
+    /// let top = scores
+    ///     .into_iter()
+    ///     .k_smallest_sorted_by_partial_key(2, |(_, score)| -score);
+    /// assert_eq!(top, vec![("bob", 9.1), ("cid", 8.3)]);
+    /// ```
+    fn k_smallest_sorted_by_partial_key<K, F>(self, k: usize, key_fn: F) -> Vec<Self::Item>
+    where
+        Self: Sized,
+        K: PartialOrd,
+        F: FnMut(&Self::Item) -> K,
+    {
+        (self
+            .k_smallest_sorted_by_partial_key_with(k, key_fn)
+            .into_iter())
+        .map(|(item, _)| item)
+        .collect()
+    }
+
+    /// Returns the `k` elements with the smallest keys together with their keys, in ascending
+    /// key order.
+    ///
+    /// Similar to `k_smallest_sorted_by_partial_key`, but each selected element is returned
+    /// along with the key computed for it, so an expensive key never has to be recomputed.
+    /// The key function is called exactly once per element.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The maximum number of elements to return
+    /// * `key_fn` - A function that extracts a key from each element for comparison
+    ///
+    /// # Returns
+    ///
+    /// A `Vec` of at most `k` `(item, key)` pairs, sorted by ascending key
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let words = vec!["kiwi", "fig", "banana", "plum"];
+    /// let shortest = words.into_iter().k_smallest_sorted_by_partial_key_with(2, |w| w.len());
+    /// assert_eq!(shortest, vec![("fig", 3), ("kiwi", 4)]);
+    /// ```
+    fn k_smallest_sorted_by_partial_key_with<K, F>(
+        self,
+        k: usize,
+        mut key_fn: F,
+    ) -> Vec<(Self::Item, K)>
+    where
+        Self: Sized,
+        K: PartialOrd,
+        F: FnMut(&Self::Item) -> K,
+    {
+        fn greater_than<K: PartialOrd, T>(a: &(K, usize, T), b: &(K, usize, T)) -> bool {
+            let ordering = (a.0.partial_cmp(&b.0))
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.1.cmp(&b.1));
+            ordering == std::cmp::Ordering::Greater
+        }
+
+        if k == 0 {
+            return Vec::new();
+        }
+
+        let mut iter = self
+            .enumerate()
+            .map(|(index, item)| (key_fn(&item), index, item));
+        let mut heap = iter.by_ref().take(k).collect::<Vec<_>>();
+        heapify(&mut heap, &mut greater_than);
+
+        for entry in iter {
+            if greater_than(&heap[0], &entry) {
+                heap[0] = entry;
+                sift_down(&mut heap, 0, &mut greater_than);
+            }
+        }
+
+        let mut result = Vec::with_capacity(heap.len());
+        while !heap.is_empty() {
+            let (key, _, item) = heap.swap_remove(0);
+            sift_down(&mut heap, 0, &mut greater_than);
+            result.push((item, key));
+        }
+        result.reverse();
+        result
+    }
+
+    /// Returns the median element according to the specified key function.
+    ///
+    /// The median is found with quickselect in expected `O(n)` time, without fully sorting the
+    /// elements. For an even number of elements, the lower of the two middle elements is
+    /// returned. When the comparison returns `None` (indicating incomparable values like NaN),
+    /// it treats them as equal.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_fn` - A function that extracts a key from each element for comparison
+    ///
+    /// # Returns
+    ///
+    /// * `Some(item)` - The element whose key is the (lower) median
+    /// * `None` - If the iterator is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let people = vec![("Alice", 25), ("Bob", 30), ("Charlie", 20)];
+    /// let median = people.into_iter().median_by_partial_key(|(_, age)| *age);
+    /// assert_eq!(median, Some(("Alice", 25)));
+    /// ```
+    fn median_by_partial_key<K, F>(self, key_fn: F) -> Option<Self::Item>
+    where
+        Self: Sized,
+        K: PartialOrd,
+        F: FnMut(&Self::Item) -> K,
+    {
+        let items = self.collect::<Vec<_>>();
+        if items.is_empty() {
+            return None;
+        }
+
+        let keys = items.iter().map(key_fn).collect::<Vec<_>>();
+        let mut indices = (0..items.len()).collect::<Vec<_>>();
+        let nth = (items.len() - 1) / 2;
+        select_nth_by_partial_key(&keys, &mut indices, nth);
+        items.into_iter().nth(indices[nth])
+    }
+
+    /// Returns the median of numeric elements.
+    ///
+    /// Elements are converted to `f64` and the median is found with quickselect in expected
+    /// `O(n)` time. For an even number of elements, the mean of the two middle values is
+    /// returned. NaN values are treated as equal to everything, as in `min_by_partial_key`.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(median)` - The median value
+    /// * `None` - If the iterator is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// assert_eq!(vec![3.0, 1.0, 2.0].into_iter().median_partial(), Some(2.0));
+    /// assert_eq!(vec![4, 1, 3, 2].into_iter().median_partial(), Some(2.5));
+    /// ```
+    fn median_partial(self) -> Option<f64>
+    where
+        Self: Sized,
+        Self::Item: Into<f64>,
+    {
+        let values = self.map(Into::into).collect::<Vec<f64>>();
+        if values.is_empty() {
+            return None;
+        }
+
+        let mut indices = (0..values.len()).collect::<Vec<_>>();
+        let nth = (values.len() - 1) / 2;
+        select_nth_by_partial_key(&values, &mut indices, nth);
+        let lower = values[indices[nth]];
+        if values.len() % 2 == 1 {
+            return Some(lower);
+        }
+
+        select_nth_by_partial_key(&values, &mut indices[nth + 1..], 0);
+        let upper = values[indices[nth + 1]];
+        Some((lower + upper) / 2.0)
+    }
+
+    /// Returns the `q`-th quantile of the float keys extracted by the specified function.
+    ///
+    /// Keys are converted to `f64` so that neighbouring keys can be interpolated. The quantile
+    /// position is `q * (n - 1)` in ascending key order. When it falls between two
+    /// elements, `interpolation` decides the result: `Linear` interpolates between both keys,
+    /// `Lower` and `Higher` pick one of them, `Nearest` picks the closer one (the lower one on
+    /// ties) and `Midpoint` averages them. The neighbouring keys are found with quickselect in
+    /// expected `O(n)` time. When the comparison returns `None` (indicating incomparable values
+    /// like NaN), it treats them as equal.
+    ///
+    /// # Arguments
+    ///
+    /// * `q` - The quantile to compute, between `0.0` and `1.0` inclusive
+    /// * `interpolation` - How to combine the two keys surrounding the quantile position
+    /// * `key_fn` - A function that extracts a key convertible to `f64` from each element
+    ///
+    /// # Returns
+    ///
+    /// * `Some(value)` - The quantile of the keys
+    /// * `None` - If the iterator is empty
+    ///
+    /// # Panics
+    ///
+    /// Panics if `q` is not within `[0.0, 1.0]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::prelude::*;
+    ///
+    /// let latencies_ms = vec![12.0, 15.0, 11.0, 40.0, 13.0];
+    /// let p95 = latencies_ms
+    ///     .iter()
+    ///     .quantile_by_float_key(0.95, QuantileInterpolation::Linear, |&&x| x);
+    /// assert_eq!(p95, Some(35.0));
+    ///
+    /// let p50 = latencies_ms
+    ///     .iter()
+    ///     .quantile_by_float_key(0.5, QuantileInterpolation::Lower, |&&x| x);
+    /// assert_eq!(p50, Some(13.0));
+    /// ```
+    fn quantile_by_float_key<K, F>(
+        self,
+        q: f64,
+        interpolation: QuantileInterpolation,
+        mut key_fn: F,
+    ) -> Option<f64>
+    where
+        Self: Sized,
+        K: Into<f64>,
+        F: FnMut(&Self::Item) -> K,
+    {
+        assert!((0.0..=1.0).contains(&q), "quantile must be within [0, 1]");
+
+        let keys = self.map(|item| key_fn(&item).into()).collect::<Vec<f64>>();
+        if keys.is_empty() {
+            return None;
+        }
+
+        let position = q * (keys.len() - 1) as f64;
+        let lower_index = position.floor() as usize;
+        let fraction = position - lower_index as f64;
+
+        let mut indices = (0..keys.len()).collect::<Vec<_>>();
+        select_nth_by_partial_key(&keys, &mut indices, lower_index);
+        let lower = keys[indices[lower_index]];
+        if fraction == 0.0 {
+            return Some(lower);
+        }
+
+        select_nth_by_partial_key(&keys, &mut indices[lower_index + 1..], 0);
+        let upper = keys[indices[lower_index + 1]];
+
+        Some(match interpolation {
+            QuantileInterpolation::Linear => lower + (upper - lower) * fraction,
+            QuantileInterpolation::Lower => lower,
+            QuantileInterpolation::Higher => upper,
+            QuantileInterpolation::Nearest if fraction <= 0.5 => lower,
+            QuantileInterpolation::Nearest => upper,
+            QuantileInterpolation::Midpoint => (lower + upper) / 2.0,
+        })
+    }
+
+    /// Returns the rank of each element, in input order, according to the specified key.
+    ///
+    /// Ranks are 1-based, with the smallest key receiving rank 1. Elements with equal keys
+    /// are ranked according to `ties`: `Average` assigns the mean of the ranks they span, `Min`
+    /// and `Max` assign the lowest or highest of those ranks, and `Dense` assigns consecutive
+    /// ranks to distinct key groups without gaps. When the comparison returns `None`
+    /// (indicating incomparable values like NaN), it treats them as equal.
+    ///
+    /// # Arguments
+    ///
+    /// * `ties` - How to rank elements with equal keys
+    /// * `key_fn` - A function that extracts a key from each element for comparison
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<f64>` holding the rank of each element, in input order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::prelude::*;
+    ///
+    /// let scores = vec![10.0, 30.0, 20.0, 30.0];
+    /// let ranks = scores.iter().ranks_by_partial_key(RankTies::Average, |&&x| x);
+    /// assert_eq!(ranks, vec![1.0, 3.5, 2.0, 3.5]);
+    ///
+    /// let ranks = scores.iter().ranks_by_partial_key(RankTies::Dense, |&&x| x);
+    /// assert_eq!(ranks, vec![1.0, 3.0, 2.0, 3.0]);
+    /// ```
+    fn ranks_by_partial_key<K, F>(self, ties: RankTies, mut key_fn: F) -> Vec<f64>
+    where
+        Self: Sized,
+        K: PartialOrd,
+        F: FnMut(&Self::Item) -> K,
+    {
+        let keys = self.map(|item| key_fn(&item)).collect::<Vec<_>>();
+        let sorted = sort_indices_by_partial_key(&keys);
+        let mut ranks = vec![0.0; keys.len()];
+
+        let mut start = 0;
+        let mut group = 0;
+        while start < sorted.len() {
+            let end = (start + 1..sorted.len())
+                .find(|&i| {
+                    keys[sorted[i - 1]].partial_cmp(&keys[sorted[i]])
+                        == Some(std::cmp::Ordering::Less)
+                })
+                .unwrap_or(sorted.len());
+            group += 1;
+
+            let rank = match ties {
+                RankTies::Average => (start + 1 + end) as f64 / 2.0,
+                RankTies::Min => (start + 1) as f64,
+                RankTies::Max => end as f64,
+                RankTies::Dense => group as f64,
+            };
+            for &index in &sorted[start..end] {
+                ranks[index] = rank;
+            }
+            start = end;
+        }
+        ranks
+    }
+
+    /// Computes summary statistics of numeric elements in a single pass.
+    ///
+    /// Elements are converted to `f64`. The mean and variance are accumulated with Welford's
+    /// online algorithm for numerical stability. NaN values are skipped and counted separately
+    /// in `nan_count`.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(summary)` - The count, min, max, mean, population variance and standard deviation
+    /// * `None` - If the iterator yields no non-NaN values
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let summary = vec![2.0, 4.0, f64::NAN, 4.0, 6.0].into_iter().describe().unwrap();
+    /// assert_eq!(summary.count, 4);
+    /// assert_eq!(summary.nan_count, 1);
+    /// assert_eq!((summary.min, summary.max), (2.0, 6.0));
+    /// assert_eq!(summary.mean, 4.0);
+    /// assert_eq!(summary.variance, 2.0);
+    /// ```
+    fn describe(self) -> Option<crate::stats::Summary>
+    where
+        Self: Sized,
+        Self::Item: Into<f64>,
+    {
+        let mut welford = crate::stats::Welford::default();
+        let mut nan_count = 0;
+        let mut min = f64::INFINITY;
+        let mut max = f64::NEG_INFINITY;
+
+        for value in self.map(Into::into) {
+            if value.is_nan() {
+                nan_count += 1;
+                continue;
+            }
+            welford.push(value);
+            min = min.min(value);
+            max = max.max(value);
+        }
+
+        let variance = welford.variance()?;
+        Some(crate::stats::Summary {
+            count: welford.count,
+            nan_count,
+            min,
+            max,
+            mean: welford.mean()?,
+            variance,
+            stddev: variance.sqrt(),
+        })
+    }
+
+    /// Returns the arithmetic mean of numeric elements.
+    ///
+    /// Elements are converted to `f64` and accumulated with Welford's online algorithm, which
+    /// avoids the precision loss of summing first and dividing afterwards. A NaN element makes
+    /// the result NaN.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(mean)` - The mean of all elements
+    /// * `None` - If the iterator is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// assert_eq!(vec![1.0, 2.0, 6.0].into_iter().mean(), Some(3.0));
+    /// assert_eq!(Vec::<f64>::new().into_iter().mean(), None);
+    /// ```
+    fn mean(self) -> Option<f64>
+    where
+        Self: Sized,
+        Self::Item: Into<f64>,
+    {
+        let mut welford = crate::stats::Welford::default();
+        self.for_each(|item| welford.push(item.into()));
+        welford.mean()
+    }
+
+    /// Returns the population variance of numeric elements.
+    ///
+    /// Elements are converted to `f64` and accumulated with Welford's online algorithm in a
+    /// single pass. The sum of squared deviations is divided by the number of elements. A NaN
+    /// element makes the result NaN.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(variance)` - The population variance of all elements
+    /// * `None` - If the iterator is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// assert_eq!(vec![2, 4, 4, 4, 5, 5, 7, 9].into_iter().variance(), Some(4.0));
+    /// ```
+    fn variance(self) -> Option<f64>
+    where
+        Self: Sized,
+        Self::Item: Into<f64>,
+    {
+        let mut welford = crate::stats::Welford::default();
+        self.for_each(|item| welford.push(item.into()));
+        welford.variance()
+    }
+
+    /// Returns the population standard deviation of numeric elements.
+    ///
+    /// This is the square root of `variance`, with the same handling of empty input and NaN
+    /// elements.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(stddev)` - The population standard deviation of all elements
+    /// * `None` - If the iterator is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// assert_eq!(vec![2, 4, 4, 4, 5, 5, 7, 9].into_iter().stddev(), Some(2.0));
+    /// ```
+    fn stddev(self) -> Option<f64>
+    where
+        Self: Sized,
+        Self::Item: Into<f64>,
+    {
+        self.variance().map(f64::sqrt)
+    }
+
+    /// Returns the sum of numeric elements using compensated summation.
+    ///
+    /// Elements are converted to `f64` and added with the Kahan–Babuška (Neumaier) algorithm,
+    /// which tracks the rounding error of each addition. This keeps the result accurate when
+    /// summing many values of very different magnitudes, where a naive `sum` loses precision.
+    ///
+    /// # Returns
+    ///
+    /// The compensated sum of all elements, or `0.0` if the iterator is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let values = vec![1.0, 1e100, 1.0, -1e100];
+    /// assert_eq!(values.iter().sum::<f64>(), 0.0);
+    /// assert_eq!(values.into_iter().sum_compensated(), 2.0);
+    /// ```
+    fn sum_compensated(self) -> f64
+    where
+        Self: Sized,
+        Self::Item: Into<f64>,
+    {
+        let mut sum = crate::stats::NeumaierSum::default();
+        self.for_each(|item| sum.push(item.into()));
+        sum.total()
+    }
+
+    /// Returns the sum of the finite numeric elements, along with the number of skipped ones.
+    ///
+    /// Elements are converted to `f64`. NaN and infinite values are skipped instead of
+    /// poisoning the result; the remaining values are added with compensated summation as in
+    /// `sum_compensated`.
+    ///
+    /// # Returns
+    ///
+    /// A `(sum, skipped)` tuple, where `sum` is `0.0` if no finite values were found
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let samples = vec![1.5, f64::NAN, 2.5, f64::INFINITY];
+    /// assert_eq!(samples.into_iter().sum_finite(), (4.0, 2));
+    /// ```
+    fn sum_finite(self) -> (f64, usize)
+    where
+        Self: Sized,
+        Self::Item: Into<f64>,
+    {
+        let mut sum = crate::stats::NeumaierSum::default();
+        let mut skipped = 0;
+        for value in self.map(Into::into) {
+            if value.is_finite() {
+                sum.push(value);
+            } else {
+                skipped += 1;
+            }
+        }
+        (sum.total(), skipped)
+    }
+
+    /// Returns the mean of the finite numeric elements, along with the number of skipped ones.
+    ///
+    /// Elements are converted to `f64`. NaN and infinite values are skipped; the remaining
+    /// values are averaged with Welford's online algorithm as in `mean`.
+    ///
+    /// # Returns
+    ///
+    /// A `(mean, skipped)` tuple, where `mean` is `None` if no finite values were found
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let samples = vec![1.0, f64::NAN, 3.0];
+    /// assert_eq!(samples.into_iter().mean_finite(), (Some(2.0), 1));
+    /// ```
+    fn mean_finite(self) -> (Option<f64>, usize)
+    where
+        Self: Sized,
+        Self::Item: Into<f64>,
+    {
+        let mut welford = crate::stats::Welford::default();
+        let mut skipped = 0;
+        for value in self.map(Into::into) {
+            if value.is_finite() {
+                welford.push(value);
+            } else {
+                skipped += 1;
+            }
+        }
+        (welford.mean(), skipped)
+    }
+
+    /// Returns the geometric mean of numeric elements.
+    ///
+    /// Elements are converted to `f64` and the mean is computed in log space to avoid overflow
+    /// of the running product. The geometric mean is only defined for non-negative values; a
+    /// zero element makes the result `0.0`, an infinite element makes it infinite, and a NaN
+    /// element makes it NaN.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(mean)` - The geometric mean of all elements
+    /// * `None` - If the iterator is empty or contains a negative value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let growth = vec![1.0, 4.0, 16.0];
+    /// assert_eq!(growth.into_iter().geometric_mean(), Some(4.0));
+    /// assert_eq!(vec![2.0, -2.0].into_iter().geometric_mean(), None);
+    /// ```
+    fn geometric_mean(self) -> Option<f64>
+    where
+        Self: Sized,
+        Self::Item: Into<f64>,
+    {
+        let mut log_sum = crate::stats::NeumaierSum::default();
+        let mut count = 0;
+        let mut has_zero = false;
+        for value in self.map(Into::into) {
+            if value < 0.0 {
+                return None;
+            }
+            if value == 0.0 {
+                has_zero = true;
+            } else {
+                log_sum.push(value.ln());
+            }
+            count += 1;
+        }
+
+        match (count, has_zero) {
+            (0, _) => None,
+            (_, true) => Some(0.0),
+            (_, false) => Some((log_sum.total() / count as f64).exp()),
+        }
+    }
+
+    /// Returns the harmonic mean of numeric elements.
+    ///
+    /// Elements are converted to `f64`. The harmonic mean is only defined for strictly positive
+    /// values, so zero or negative elements yield `None`. A NaN element makes the result NaN.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(mean)` - The harmonic mean of all elements
+    /// * `None` - If the iterator is empty or contains a zero or negative value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// assert_eq!(vec![1.0, 4.0, 4.0].into_iter().harmonic_mean(), Some(2.0));
+    /// assert_eq!(vec![1.0, 0.0].into_iter().harmonic_mean(), None);
+    /// ```
+    fn harmonic_mean(self) -> Option<f64>
+    where
+        Self: Sized,
+        Self::Item: Into<f64>,
+    {
+        let mut reciprocal_sum = crate::stats::NeumaierSum::default();
+        let mut count = 0;
+        for value in self.map(Into::into) {
+            if value <= 0.0 {
+                return None;
+            }
+            reciprocal_sum.push(value.recip());
+            count += 1;
+        }
+        (count > 0).then(|| count as f64 / reciprocal_sum.total())
+    }
+
+    /// Returns the population covariance of `(x, y)` pairs.
+    ///
+    /// Both coordinates are converted to `f64` and the co-moments are accumulated in a single
+    /// streaming pass with a Welford-style update, so the series never need to be collected.
+    /// A NaN coordinate makes the result NaN.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(covariance)` - The population covariance of the pairs
+    /// * `None` - If the iterator is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let pairs = vec![(1.0, 2.0), (2.0, 4.0), (3.0, 6.0)];
+    /// let covariance = pairs.into_iter().covariance().unwrap();
+    /// assert!((covariance - 4.0 / 3.0).abs() < 1e-12);
+    /// ```
+    fn covariance<X, Y>(self) -> Option<f64>
+    where
+        Self: Sized + Iterator<Item = (X, Y)>,
+        X: Into<f64>,
+        Y: Into<f64>,
+    {
+        let mut moments = crate::stats::CoMoments::default();
+        self.for_each(|(x, y)| moments.push(x.into(), y.into()));
+        moments.covariance()
+    }
+
+    /// Returns the Pearson correlation coefficient of `(x, y)` pairs.
+    ///
+    /// Computed in a single streaming pass like `covariance`. The result lies in `[-1, 1]`.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(r)` - The correlation coefficient of the pairs
+    /// * `None` - If the iterator is empty or either coordinate is constant, in which case
+    ///   the correlation is undefined
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let pairs = vec![(1.0, 10.0), (2.0, 8.0), (3.0, 6.0)];
+    /// let r = pairs.into_iter().pearson_correlation().unwrap();
+    /// assert!((r + 1.0).abs() < 1e-12);
+    /// ```
+    fn pearson_correlation<X, Y>(self) -> Option<f64>
+    where
+        Self: Sized + Iterator<Item = (X, Y)>,
+        X: Into<f64>,
+        Y: Into<f64>,
+    {
+        let mut moments = crate::stats::CoMoments::default();
+        self.for_each(|(x, y)| moments.push(x.into(), y.into()));
+        moments.correlation()
+    }
+
+    /// Fits a least-squares line `y = slope * x + intercept` to `(x, y)` pairs.
+    ///
+    /// Both coordinates are converted to `f64` and the fit is computed in a single streaming
+    /// pass from the same co-moments as `covariance`. The coefficient of determination `R²`
+    /// measures how much of the variance of `y` the line explains.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(fit)` - The slope, intercept and `R²` of the fitted line
+    /// * `None` - If the iterator is empty or all `x` values are equal
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let telemetry = vec![(0.0, 1.0), (1.0, 3.0), (2.0, 5.0), (3.0, 7.0)];
+    /// let fit = telemetry.into_iter().linear_fit().unwrap();
+    /// assert_eq!(fit.slope, 2.0);
+    /// assert_eq!(fit.intercept, 1.0);
+    /// assert_eq!(fit.r_squared, 1.0);
+    /// ```
+    fn linear_fit<X, Y>(self) -> Option<crate::stats::LinearFit>
+    where
+        Self: Sized + Iterator<Item = (X, Y)>,
+        X: Into<f64>,
+        Y: Into<f64>,
+    {
+        let mut moments = crate::stats::CoMoments::default();
+        self.for_each(|(x, y)| moments.push(x.into(), y.into()));
+        moments.linear_fit()
+    }
+
+    /// Counts numeric elements into `bins` equal-width buckets spanning `min..=max`.
+    ///
+    /// Elements are converted to `f64` and counted in a single pass. Values outside the range
+    /// are tallied in `below` and `above`, and NaN values in `nan_count`. The maximum value
+    /// belongs to the last bucket.
+    ///
+    /// # Arguments
+    ///
+    /// * `bins` - The number of buckets
+    /// * `min` - The lower bound of the first bucket
+    /// * `max` - The upper bound of the last bucket
+    ///
+    /// # Returns
+    ///
+    /// A `Histogram` with `bins + 1` edges and `bins` counts
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bins` is zero or `min` is not less than `max`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let values = vec![0.5, 1.5, 1.7, 3.9, 4.0, 7.0];
+    /// let histogram = values.into_iter().histogram(4, 0.0, 4.0);
+    /// assert_eq!(histogram.edges, vec![0.0, 1.0, 2.0, 3.0, 4.0]);
+    /// assert_eq!(histogram.counts, vec![1, 2, 0, 2]);
+    /// assert_eq!(histogram.above, 1);
+    /// ```
+    fn histogram(self, bins: usize, min: f64, max: f64) -> crate::stats::Histogram
+    where
+        Self: Sized,
+        Self::Item: Into<f64>,
+    {
+        assert!(bins > 0, "histogram needs at least one bin");
+        assert!(min < max, "histogram range must be non-empty");
+
+        let width = (max - min) / bins as f64;
+        let edges = (0..=bins)
+            .map(|i| {
+                if i == bins {
+                    max
+                } else {
+                    min + width * i as f64
+                }
+            })
+            .collect();
+        self.bucketize_by(edges)
+    }
+
+    /// Counts numeric elements into buckets delimited by the given edges.
+    ///
+    /// Similar to `histogram`, but the buckets may have arbitrary widths. Bucket `i` covers
+    /// `edges[i]..edges[i + 1]`, and the last bucket also includes its upper edge.
+    ///
+    /// # Arguments
+    ///
+    /// * `edges` - Strictly increasing bucket boundaries
+    ///
+    /// # Returns
+    ///
+    /// A `Histogram` with the given edges and `edges.len() - 1` counts
+    ///
+    /// # Panics
+    ///
+    /// Panics if fewer than two edges are given or they are not strictly increasing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let latencies = vec![3, 12, 45, 8, 250, 99];
+    /// let histogram = latencies.into_iter().bucketize_by(vec![0.0, 10.0, 100.0, 1000.0]);
+    /// assert_eq!(histogram.counts, vec![2, 3, 1]);
+    /// ```
+    fn bucketize_by(self, edges: Vec<f64>) -> crate::stats::Histogram
+    where
+        Self: Sized,
+        Self::Item: Into<f64>,
+    {
+        let mut histogram = crate::stats::Histogram::with_edges(edges);
+        self.for_each(|item| histogram.add(item.into()));
+        histogram
+    }
+
+    /// Counts the occurrences of each distinct element.
+    ///
+    /// # Returns
+    ///
+    /// A `HashMap` from each distinct element to the number of times it occurs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let counts = "abracadabra".chars().counts();
+    /// assert_eq!(counts[&'a'], 5);
+    /// assert_eq!(counts[&'b'], 2);
+    /// assert_eq!(counts.get(&'z'), None);
+    /// ```
+    fn counts(self) -> std::collections::HashMap<Self::Item, usize>
+    where
+        Self: Sized,
+        Self::Item: std::cmp::Eq + std::hash::Hash,
+    {
+        let mut counts = std::collections::HashMap::new();
+        self.for_each(|item| *counts.entry(item).or_insert(0) += 1);
+        counts
+    }
+
+    /// Counts the occurrences of each distinct key extracted from the elements.
+    ///
+    /// Similar to `counts`, but elements are grouped by the key returned by `key_fn`. The key
+    /// can also be a category, which buckets elements into a small set of named groups.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_fn` - A function that extracts a key from each element
+    ///
+    /// # Returns
+    ///
+    /// A `HashMap` from each distinct key to the number of elements that produce it
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let words = vec!["apple", "avocado", "banana", "blueberry", "cherry"];
+    /// let counts = words.iter().counts_by_key(|s| s.chars().next().unwrap());
+    /// assert_eq!(counts[&'a'], 2);
+    /// assert_eq!(counts[&'c'], 1);
+    /// ```
+    #[doc(alias = "counts_by")]
+    fn counts_by_key<K, F>(self, key_fn: F) -> std::collections::HashMap<K, usize>
+    where
+        Self: Sized,
+        K: std::cmp::Eq + std::hash::Hash,
+        F: FnMut(&Self::Item) -> K,
+    {
+        self.fold_by_key(key_fn, || 0, |count, _| *count += 1)
+    }
+
+    /// Returns the `n` most frequent elements together with their counts.
+    ///
+    /// Frequencies are counted in one pass, then the top `n` entries are selected with a
+    /// bounded heap, so the whole frequency table is never sorted. The result is ordered by
+    /// descending count; elements with equal counts are ordered by their first occurrence.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The maximum number of entries to return
+    ///
+    /// # Returns
+    ///
+    /// A `Vec` of at most `n` `(item, count)` pairs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let words = "the cat and the dog and the bird".split(' ');
+    /// assert_eq!(words.most_common(2), vec![("the", 3), ("and", 2)]);
+    /// ```
+    fn most_common(self, n: usize) -> Vec<(Self::Item, usize)>
+    where
+        Self: Sized,
+        Self::Item: std::cmp::Eq + std::hash::Hash,
+    {
+        fn worse_than<T>(a: &(usize, usize, T), b: &(usize, usize, T)) -> bool {
+            a.0.cmp(&b.0).then(b.1.cmp(&a.1)) == std::cmp::Ordering::Less
+        }
+
+        if n == 0 {
+            return Vec::new();
+        }
+
+        let mut frequencies = std::collections::HashMap::new();
+        for (index, item) in self.enumerate() {
+            frequencies.entry(item).or_insert((0, index)).0 += 1;
+        }
+
+        let mut entries =
+            (frequencies.into_iter()).map(|(item, (count, first))| (count, first, item));
+        let mut heap = entries.by_ref().take(n).collect::<Vec<_>>();
+        heapify(&mut heap, &mut worse_than);
+        for entry in entries {
+            if worse_than(&heap[0], &entry) {
+                heap[0] = entry;
+                sift_down(&mut heap, 0, &mut worse_than);
+            }
+        }
+
+        let mut result = Vec::with_capacity(heap.len());
+        while !heap.is_empty() {
+            let (count, _, item) = heap.swap_remove(0);
+            sift_down(&mut heap, 0, &mut worse_than);
+            result.push((item, count));
+        }
+        result.reverse();
+        result
+    }
+
+    /// Returns the most frequent element.
+    ///
+    /// When several elements share the highest count, the one that occurs first is returned.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(item)` - The most frequent element
+    /// * `None` - If the iterator is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let rolls = vec![4, 2, 6, 2, 4, 2];
+    /// assert_eq!(rolls.into_iter().mode(), Some(2));
+    /// ```
+    fn mode(self) -> Option<Self::Item>
+    where
+        Self: Sized,
+        Self::Item: std::cmp::Eq + std::hash::Hash,
+    {
+        self.most_common(1).pop().map(|(item, _)| item)
+    }
+
+    /// Returns all elements that share the highest frequency.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec` of the most frequent elements in order of first occurrence, empty if the
+    /// iterator is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let rolls = vec![4, 2, 6, 2, 4];
+    /// assert_eq!(rolls.into_iter().modes(), vec![4, 2]);
+    /// ```
+    fn modes(self) -> Vec<Self::Item>
+    where
+        Self: Sized,
+        Self::Item: std::cmp::Eq + std::hash::Hash,
+    {
+        let mut frequencies = std::collections::HashMap::new();
+        for (index, item) in self.enumerate() {
+            frequencies.entry(item).or_insert((0, index)).0 += 1;
+        }
+
+        let Some(max_count) = frequencies.values().map(|(count, _)| *count).max() else {
+            return Vec::new();
+        };
+        let mut modes = (frequencies.into_iter())
+            .filter(|(_, (count, _))| *count == max_count)
+            .map(|(item, (_, first))| (first, item))
+            .collect::<Vec<_>>();
+        modes.sort_unstable_by_key(|(first, _)| *first);
+        modes.into_iter().map(|(_, item)| item).collect()
+    }
+
+    /// Returns the empirical Shannon entropy of the element frequencies, in bits.
+    ///
+    /// Each distinct element is weighted by its relative frequency `p`, and the entropy is
+    /// `-Σ p log2(p)`. A stream with a single distinct value has an entropy of `0.0`, while
+    /// `n` equally frequent values give `log2(n)`.
+    ///
+    /// # Returns
+    ///
+    /// The entropy in bits, or `0.0` if the iterator is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// assert_eq!("aabb".chars().entropy(), 1.0);
+    /// assert_eq!("abcd".chars().entropy(), 2.0);
+    /// assert_eq!("aaaa".chars().entropy(), 0.0);
+    /// ```
+    fn entropy(self) -> f64
+    where
+        Self: Sized,
+        Self::Item: std::cmp::Eq + std::hash::Hash,
+    {
+        shannon_entropy(&self.counts())
+    }
+
+    /// Returns the empirical Shannon entropy of the key frequencies, in bits.
+    ///
+    /// Similar to `entropy`, but elements are grouped by the key returned by `key_fn`.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_fn` - A function that extracts a key from each element
+    ///
+    /// # Returns
+    ///
+    /// The entropy in bits, or `0.0` if the iterator is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let numbers = vec![1, 2, 3, 4];
+    /// assert_eq!(numbers.iter().entropy_by_key(|&&x| x % 2), 1.0);
+    /// ```
+    fn entropy_by_key<K, F>(self, key_fn: F) -> f64
+    where
+        Self: Sized,
+        K: std::cmp::Eq + std::hash::Hash,
+        F: FnMut(&Self::Item) -> K,
+    {
+        shannon_entropy(&self.counts_by_key(key_fn))
+    }
+
+    /// Returns an iterator that yields the mean of the last `size` numeric elements at each
+    /// position.
+    ///
+    /// Elements are converted to `f64`, and a running sum is maintained so each step costs
+    /// `O(1)`. Until `size` elements have been seen, `warm_up` decides what is yielded:
+    /// `WarmUp::Empty` yields `None`, and `WarmUp::Partial` yields the mean of the elements
+    /// seen so far. NaN and infinite values only affect the windows that contain them.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The number of elements in each window
+    /// * `warm_up` - What to yield before the first full window
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields one `Option<f64>` per element
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::prelude::*;
+    ///
+    /// let readings = vec![1.0, 3.0, 5.0, 7.0];
+    /// let smoothed: Vec<Option<f64>> = readings.iter().copied().rolling_mean(2, WarmUp::Empty)
+    ///     .collect();
+    /// assert_eq!(smoothed, vec![None, Some(2.0), Some(4.0), Some(6.0)]);
+    ///
+    /// let smoothed: Vec<Option<f64>> = readings.into_iter().rolling_mean(3, WarmUp::Partial)
+    ///     .collect();
+    /// assert_eq!(smoothed, vec![Some(1.0), Some(2.0), Some(3.0), Some(5.0)]);
+    /// ```
+    fn rolling_mean(self, size: usize, warm_up: WarmUp) -> RollingMean<Self>
+    where
+        Self: Sized,
+        Self::Item: Into<f64>,
+    {
+        RollingMean::new(self, size, warm_up)
+    }
+
+    /// Returns an iterator that yields the exponentially weighted moving average at each
+    /// position.
+    ///
+    /// Elements are converted to `f64`. The first average equals the first element, and each
+    /// following one is `alpha * value + (1 - alpha) * previous`. Larger values of `alpha` give
+    /// more weight to recent elements. The adapter keeps a single `f64` of state.
+    ///
+    /// # Arguments
+    ///
+    /// * `alpha` - The smoothing factor, within `(0, 1]`
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields one average per element
+    ///
+    /// # Panics
+    ///
+    /// Panics if `alpha` is not within `(0, 1]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let readings = vec![10.0, 20.0, 20.0];
+    /// let smoothed: Vec<f64> = readings.into_iter().ewma(0.5).collect();
+    /// assert_eq!(smoothed, vec![10.0, 15.0, 17.5]);
+    /// ```
+    fn ewma(self, alpha: f64) -> Ewma<Self>
+    where
+        Self: Sized,
+        Self::Item: Into<f64>,
+    {
+        Ewma::new(self, alpha)
+    }
+
+    /// Returns an iterator that yields the minimum of the last `size` elements at each position.
+    ///
+    /// A monotonic deque of candidate minima is maintained, so each step costs amortized `O(1)`
+    /// regardless of the window size. The first `size - 1` outputs cover the elements seen so
+    /// far. Elements that are not comparable with themselves (like NaN) are skipped; a window
+    /// holding only such elements yields its latest element. Other incomparable pairs are
+    /// treated as equal.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The number of elements in each window
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields one window minimum per element
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let series = vec![4.0, 2.0, 5.0, 7.0, 6.0, 1.0];
+    /// let minima: Vec<f64> = series.into_iter().rolling_min(3).collect();
+    /// assert_eq!(minima, vec![4.0, 2.0, 2.0, 2.0, 5.0, 1.0]);
+    /// ```
+    fn rolling_min(self, size: usize) -> RollingExtreme<Self>
+    where
+        Self: Sized,
+        Self::Item: PartialOrd + Clone,
+    {
+        RollingExtreme::new(self, size, std::cmp::Ordering::Greater)
+    }
+
+    /// Returns an iterator that yields the maximum of the last `size` elements at each position.
+    ///
+    /// Similar to `rolling_min`, with the same amortized `O(1)` cost per element.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The number of elements in each window
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields one window maximum per element
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let series = vec![4, 2, 5, 7, 6, 1];
+    /// let maxima: Vec<i32> = series.into_iter().rolling_max(2).collect();
+    /// assert_eq!(maxima, vec![4, 4, 5, 7, 7, 6]);
+    /// ```
+    fn rolling_max(self, size: usize) -> RollingExtreme<Self>
+    where
+        Self: Sized,
+        Self::Item: PartialOrd + Clone,
+    {
+        RollingExtreme::new(self, size, std::cmp::Ordering::Less)
+    }
+
+    /// Returns an iterator that yields the element with the minimum key among the last `size`
+    /// elements at each position.
+    ///
+    /// Similar to `rolling_min`, but elements are compared by the key returned by `key_fn`,
+    /// which is called exactly once per element. Keys that are not comparable with themselves
+    /// (like NaN) are skipped in the same way. If several elements in a window share the
+    /// minimum key, the earliest one is yielded.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The number of elements in each window
+    /// * `key_fn` - A function that extracts a key from each element for comparison
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields one element per input element
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let quotes = vec![("a", 10.5), ("b", 9.75), ("c", f64::NAN), ("d", 11.0)];
+    /// let cheapest: Vec<&str> = quotes
+    ///     .iter()
+    ///     .rolling_min_by_partial_key(2, |(_, price)| *price)
+    ///     .map(|(name, _)| *name)
+    ///     .collect();
+    /// assert_eq!(cheapest, vec!["a", "b", "b", "d"]);
+    /// ```
+    fn rolling_min_by_partial_key<K, F>(
+        self,
+        size: usize,
+        key_fn: F,
+    ) -> RollingExtremeByPartialKey<Self, K, F>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        K: PartialOrd,
+        F: FnMut(&Self::Item) -> K,
+    {
+        RollingExtremeByPartialKey::new(self, size, key_fn, std::cmp::Ordering::Greater)
+    }
+
+    /// Returns an iterator that yields the element with the maximum key among the last `size`
+    /// elements at each position.
+    ///
+    /// Similar to `rolling_min_by_partial_key`, with the same handling of incomparable keys and
+    /// ties.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The number of elements in each window
+    /// * `key_fn` - A function that extracts a key from each element for comparison
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields one element per input element
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let words = vec!["to", "be", "or", "not", "to", "be"];
+    /// let longest: Vec<&str> = words
+    ///     .into_iter()
+    ///     .rolling_max_by_partial_key(3, |w| w.len())
+    ///     .collect();
+    /// assert_eq!(longest, vec!["to", "to", "to", "not", "not", "not"]);
+    /// ```
+    fn rolling_max_by_partial_key<K, F>(
+        self,
+        size: usize,
+        key_fn: F,
+    ) -> RollingExtremeByPartialKey<Self, K, F>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        K: PartialOrd,
+        F: FnMut(&Self::Item) -> K,
+    {
+        RollingExtremeByPartialKey::new(self, size, key_fn, std::cmp::Ordering::Less)
+    }
+
+    /// Returns an iterator that standardizes numeric elements using a known mean and standard
+    /// deviation.
+    ///
+    /// Each element is converted to `f64` and mapped to `(value - mean) / stddev`. The
+    /// parameters typically come from a previous `describe` call. When `stddev` is zero, every
+    /// element maps to `0.0` instead of dividing by zero.
+    ///
+    /// # Arguments
+    ///
+    /// * `mean` - The mean to subtract
+    /// * `stddev` - The standard deviation to divide by
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields one z-score per element
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let values = vec![2.0, 4.0, 6.0];
+    /// let summary = values.iter().copied().describe().unwrap();
+    /// let scores: Vec<f64> = values
+    ///     .into_iter()
+    ///     .zscore(summary.mean, summary.stddev)
+    ///     .map(|z| (z * 1000.0).round() / 1000.0)
+    ///     .collect();
+    /// assert_eq!(scores, vec![-1.225, 0.0, 1.225]);
+    /// ```
+    fn zscore(self, mean: f64, stddev: f64) -> ZScore<Self>
+    where
+        Self: Sized,
+        Self::Item: Into<f64>,
+    {
+        ZScore::new(self, mean, stddev)
+    }
+
+    /// Returns an iterator that standardizes numeric elements using their own mean and
+    /// standard deviation.
+    ///
+    /// This is a two-pass convenience for `zscore`: a clone of the iterator is consumed first
+    /// to compute the statistics with `describe`, then the original iterator is standardized.
+    /// NaN values are ignored when computing the statistics and map to NaN.
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields one z-score per element
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let values = vec![1.0, 3.0];
+    /// let scores: Vec<f64> = values.into_iter().standardize().collect();
+    /// assert_eq!(scores, vec![-1.0, 1.0]);
+    /// ```
+    fn standardize(self) -> ZScore<Self>
+    where
+        Self: Sized + Clone,
+        Self::Item: Into<f64>,
+    {
+        let (mean, stddev) = (self.clone().describe()).map_or((f64::NAN, f64::NAN), |summary| {
+            (summary.mean, summary.stddev)
+        });
+        ZScore::new(self, mean, stddev)
+    }
+
+    /// Returns an iterator that rescales numeric elements from `min..=max` into `[0, 1]`.
+    ///
+    /// Each element is converted to `f64` and mapped to `(value - min) / (max - min)`. Values
+    /// outside the given range are not clamped. For a constant series, where `min` equals
+    /// `max`, every element maps to `0.0` instead of dividing by zero.
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - The value that maps to `0.0`
+    /// * `max` - The value that maps to `1.0`
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields one rescaled value per element
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let celsius = vec![0, 25, 100];
+    /// let scaled: Vec<f64> = celsius.into_iter().normalize_min_max(0.0, 100.0).collect();
+    /// assert_eq!(scaled, vec![0.0, 0.25, 1.0]);
+    /// ```
+    fn normalize_min_max(self, min: f64, max: f64) -> NormalizeMinMax<Self>
+    where
+        Self: Sized,
+        Self::Item: Into<f64>,
+    {
+        NormalizeMinMax::new(self, min, max)
+    }
+
+    /// Returns an iterator that rescales numeric elements into `[0, 1]` using their own minimum
+    /// and maximum.
+    ///
+    /// This is a two-pass convenience for `normalize_min_max`: a clone of the iterator is
+    /// consumed first to find the extremes with `describe`, then the original iterator is
+    /// rescaled. NaN values are ignored when finding the extremes and map to NaN.
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields one rescaled value per element
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let values = vec![10.0, 15.0, 20.0];
+    /// let scaled: Vec<f64> = values.into_iter().normalize().collect();
+    /// assert_eq!(scaled, vec![0.0, 0.5, 1.0]);
+    /// ```
+    fn normalize(self) -> NormalizeMinMax<Self>
+    where
+        Self: Sized + Clone,
+        Self::Item: Into<f64>,
+    {
+        let (min, max) = (self.clone().describe())
+            .map_or((f64::NAN, f64::NAN), |summary| (summary.min, summary.max));
+        NormalizeMinMax::new(self, min, max)
+    }
+
+    /// Returns an iterator that clamps each element into the range `lo..=hi`.
+    ///
+    /// Elements below `lo` are replaced by `lo` and elements above `hi` by `hi`. Elements that
+    /// cannot be compared with the bounds (like NaN) are handled according to `nan_policy`:
+    /// passed through unchanged, replaced by a given value, or dropped from the stream.
+    ///
+    /// # Arguments
+    ///
+    /// * `lo` - The lower bound
+    /// * `hi` - The upper bound
+    /// * `nan_policy` - What to do with elements that are incomparable with the bounds
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields the clamped elements
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lo` is greater than `hi` or the bounds are incomparable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::prelude::*;
+    ///
+    /// let readings = vec![-3.0, 0.5, f64::NAN, 1.7];
+    /// let cleaned: Vec<f64> = readings
+    ///     .into_iter()
+    ///     .clamp_values(0.0, 1.0, NanPolicy::Drop)
+    ///     .collect();
+    /// assert_eq!(cleaned, vec![0.0, 0.5, 1.0]);
+    /// ```
+    fn clamp_values(
+        self,
+        lo: Self::Item,
+        hi: Self::Item,
+        nan_policy: NanPolicy<Self::Item>,
+    ) -> ClampValues<Self>
+    where
+        Self: Sized,
+        Self::Item: PartialOrd + Clone,
+    {
+        ClampValues::new(self, lo, hi, nan_policy)
+    }
+
+    /// Returns an iterator that replaces NaN values according to `fill`.
+    ///
+    /// Elements are converted to `f64`. With `Fill::Value`, each NaN is replaced by the given
+    /// value; with `Fill::Previous`, it is replaced by the last non-NaN value seen. A NaN that
+    /// appears before any valid value is kept when filling from the previous value.
+    ///
+    /// # Arguments
+    ///
+    /// * `fill` - How to replace NaN values
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields one `f64` per element
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::prelude::*;
+    ///
+    /// let samples = vec![1.0, f64::NAN, 3.0, f64::NAN];
+    /// let zeroed: Vec<f64> = samples.iter().copied().nan_to(Fill::Value(0.0)).collect();
+    /// assert_eq!(zeroed, vec![1.0, 0.0, 3.0, 0.0]);
+    ///
+    /// let held: Vec<f64> = samples.into_iter().nan_to(Fill::Previous).collect();
+    /// assert_eq!(held, vec![1.0, 1.0, 3.0, 3.0]);
+    /// ```
+    fn nan_to(self, fill: Fill) -> ReplaceNonFinite<Self>
+    where
+        Self: Sized,
+        Self::Item: Into<f64>,
+    {
+        ReplaceNonFinite::new(self, fill, false)
+    }
+
+    /// Returns an iterator that replaces NaN and infinite values according to `fill`.
+    ///
+    /// Similar to `nan_to`, but positive and negative infinity are replaced as well, and
+    /// `Fill::Previous` uses the last finite value seen.
+    ///
+    /// # Arguments
+    ///
+    /// * `fill` - How to replace non-finite values
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields one `f64` per element
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::prelude::*;
+    ///
+    /// let samples = vec![2.0, f64::INFINITY, f64::NAN, 4.0];
+    /// let cleaned: Vec<f64> = samples.into_iter().replace_non_finite(Fill::Previous).collect();
+    /// assert_eq!(cleaned, vec![2.0, 2.0, 2.0, 4.0]);
+    /// ```
+    fn replace_non_finite(self, fill: Fill) -> ReplaceNonFinite<Self>
+    where
+        Self: Sized,
+        Self::Item: Into<f64>,
+    {
+        ReplaceNonFinite::new(self, fill, true)
+    }
+
+    /// Returns an iterator that skips NaN and infinite values.
+    ///
+    /// Works with any element implementing `FloatLike`, which covers `f32`, `f64` and
+    /// references to them, so it can be used directly on `slice::iter()`.
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields only the finite elements
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let samples = vec![1.0, f64::NAN, 2.0, f64::NEG_INFINITY];
+    /// let finite: Vec<&f64> = samples.iter().filter_finite().collect();
+    /// assert_eq!(finite, vec![&1.0, &2.0]);
+    /// ```
+    fn filter_finite(self) -> FilterFinite<Self>
+    where
+        Self: Sized,
+        Self::Item: FloatLike,
+    {
+        FilterFinite::new(self)
+    }
+
+    /// Returns the element that gives the minimum value from the specified float key function,
+    /// ordering keys with `f64::total_cmp`.
+    ///
+    /// Unlike `min_by_partial_key`, NaN keys have a deterministic position in the order:
+    /// positive NaN sorts above positive infinity and negative NaN below negative infinity.
+    /// If several elements are equally minimal, the first one is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_fn` - A function that extracts an `f64` key from each element
+    ///
+    /// # Returns
+    ///
+    /// * `Some(item)` - The element that produces the minimum key value
+    /// * `None` - If the iterator is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let numbers = vec![f64::NAN, 2.5, -1.0];
+    /// assert_eq!(numbers.iter().min_by_float_key(|&&x| x), Some(&-1.0));
+    /// ```
+    fn min_by_float_key<F>(self, mut key_fn: F) -> Option<Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> f64,
+    {
+        self.min_by(|x, y| key_fn(x).total_cmp(&key_fn(y)))
+    }
+
+    /// Returns the element that gives the maximum value from the specified float key function,
+    /// ordering keys with `f64::total_cmp`.
+    ///
+    /// Similar to `min_by_float_key`. Note that a positive NaN key is greater than every other
+    /// value under this order. If several elements are equally maximal, the last one is
+    /// returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_fn` - A function that extracts an `f64` key from each element
+    ///
+    /// # Returns
+    ///
+    /// * `Some(item)` - The element that produces the maximum key value
+    /// * `None` - If the iterator is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let numbers = vec![1.0, f64::INFINITY, 2.5];
+    /// assert_eq!(numbers.iter().max_by_float_key(|&&x| x), Some(&f64::INFINITY));
+    ///
+    /// let with_nan = vec![1.0, f64::NAN];
+    /// assert!(with_nan.iter().max_by_float_key(|&&x| x).unwrap().is_nan());
+    /// ```
+    fn max_by_float_key<F>(self, mut key_fn: F) -> Option<Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> f64,
+    {
+        self.max_by(|x, y| key_fn(x).total_cmp(&key_fn(y)))
+    }
+
+    /// Returns an iterator over all elements sorted in ascending order of the specified float
+    /// key, ordering keys with `f64::total_cmp`.
+    ///
+    /// The sort is stable and the key is computed once per element. Positive NaN keys sort
+    /// last and negative NaN keys sort first.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_fn` - A function that extracts an `f64` key from each element
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields the elements in ascending key order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let numbers = vec![3.0, f64::NAN, -0.0, 0.0, -2.0];
+    /// let sorted: Vec<f64> = numbers.into_iter().sorted_by_float_key(|&x| x).collect();
+    /// assert_eq!(&sorted[..4], &[-2.0, -0.0, 0.0, 3.0]);
+    /// assert!(sorted[4].is_nan());
+    /// ```
+    fn sorted_by_float_key<F>(self, mut key_fn: F) -> SortedByFloatKey<Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> f64,
+    {
+        let mut keyed = self.map(|item| (key_fn(&item), item)).collect::<Vec<_>>();
+        keyed.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        SortedByFloatKey::new(keyed)
+    }
+
+    /// Returns an iterator over the running totals of the elements.
+    ///
+    /// The first element is yielded as is, and each later element is added to the total so
+    /// far. Integer overflow behaves like the `+` operator; use `cumsum_checked`,
+    /// `cumsum_wrapping` or `cumsum_saturating` to choose an explicit overflow policy.
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields the cumulative sum at each position
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let deposits = vec![10, 5, -3, 8];
+    /// let balance: Vec<i32> = deposits.into_iter().cumsum().collect();
+    /// assert_eq!(balance, vec![10, 15, 12, 20]);
+    ///
+    /// let steps = vec![0.5, 0.25, 0.25];
+    /// let position: Vec<f64> = steps.into_iter().cumsum().collect();
+    /// assert_eq!(position, vec![0.5, 0.75, 1.0]);
+    /// ```
+    fn cumsum(self) -> Cumsum<Self>
+    where
+        Self: Sized,
+        Self::Item: std::ops::Add<Output = Self::Item> + Clone,
+    {
+        Cumsum::new(self, std::ops::Add::add)
+    }
+
+    /// Returns an iterator over the running totals of integer elements, detecting overflow.
+    ///
+    /// Each position yields `Some(total)` while the sum fits in the item type. Once an addition
+    /// overflows, that position and every following one yield `None`, since the total is no
+    /// longer known.
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields `Option<Self::Item>` cumulative sums
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let bytes = vec![100u8, 100, 100, 1];
+    /// let totals: Vec<Option<u8>> = bytes.into_iter().cumsum_checked().collect();
+    /// assert_eq!(totals, vec![Some(100), Some(200), None, None]);
+    /// ```
+    fn cumsum_checked(self) -> CumsumChecked<Self>
+    where
+        Self: Sized,
+        Self::Item: IntegerLike,
+    {
+        CumsumChecked::new(self)
+    }
+
+    /// Returns an iterator over the running totals of integer elements, wrapping on overflow.
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields the cumulative sums computed with wrapping addition
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let bytes = vec![200u8, 100, 10];
+    /// let totals: Vec<u8> = bytes.into_iter().cumsum_wrapping().collect();
+    /// assert_eq!(totals, vec![200, 44, 54]);
+    /// ```
+    fn cumsum_wrapping(self) -> Cumsum<Self>
+    where
+        Self: Sized,
+        Self::Item: IntegerLike,
+    {
+        Cumsum::new(self, IntegerLike::wrapping_add)
+    }
+
+    /// Returns an iterator over the running totals of integer elements, saturating at the
+    /// numeric bounds instead of overflowing.
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields the cumulative sums computed with saturating addition
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let deltas = vec![100i8, 50, -20];
+    /// let totals: Vec<i8> = deltas.into_iter().cumsum_saturating().collect();
+    /// assert_eq!(totals, vec![100, 127, 107]);
+    /// ```
+    fn cumsum_saturating(self) -> Cumsum<Self>
+    where
+        Self: Sized,
+        Self::Item: IntegerLike,
+    {
+        Cumsum::new(self, IntegerLike::saturating_add)
+    }
+
+    /// Returns an iterator over the running maximum of the elements by key.
+    ///
+    /// Each position yields the element with the largest key seen so far, including the
+    /// current one. On ties the earliest element is kept. A key that is incomparable with the
+    /// current maximum (like NaN) never replaces it, and a leading NaN key is replaced by the
+    /// next element.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_fn` - A function that extracts a key from each element for comparison
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields the cumulative maximum at each position
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let prices = vec![10.0, 12.5, 11.0, f64::NAN, 13.0];
+    /// let peak: Vec<f64> = prices.into_iter().cummax_by_partial_key(|&x| x).collect();
+    /// assert_eq!(peak, vec![10.0, 12.5, 12.5, 12.5, 13.0]);
+    /// ```
+    fn cummax_by_partial_key<K, F>(self, key_fn: F) -> CumExtremeByPartialKey<Self, K, F>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        K: PartialOrd,
+        F: FnMut(&Self::Item) -> K,
+    {
+        CumExtremeByPartialKey::new(self, key_fn, std::cmp::Ordering::Greater)
+    }
+
+    /// Returns an iterator over the running minimum of the elements by key.
+    ///
+    /// The counterpart of `cummax_by_partial_key`: each position yields the element with the
+    /// smallest key seen so far, keeping the earliest element on ties and skipping
+    /// incomparable keys.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_fn` - A function that extracts a key from each element for comparison
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields the cumulative minimum at each position
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let temperatures = vec![("mon", 7), ("tue", 3), ("wed", 5), ("thu", 3)];
+    /// let coldest: Vec<&str> = temperatures
+    ///     .into_iter()
+    ///     .cummin_by_partial_key(|&(_, t)| t)
+    ///     .map(|(day, _)| day)
+    ///     .collect();
+    /// assert_eq!(coldest, vec!["mon", "tue", "tue", "tue"]);
+    /// ```
+    fn cummin_by_partial_key<K, F>(self, key_fn: F) -> CumExtremeByPartialKey<Self, K, F>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        K: PartialOrd,
+        F: FnMut(&Self::Item) -> K,
+    {
+        CumExtremeByPartialKey::new(self, key_fn, std::cmp::Ordering::Less)
+    }
+
+    /// Returns an iterator over the differences between consecutive numeric elements.
+    ///
+    /// Yields `item[i] - item[i - 1]` for every element after the first, so `n` elements
+    /// produce `n - 1` differences. Unlike `deltas`, which measures gaps between repeated
+    /// occurrences, this operates on the values themselves. Use `diffs_from` to also get a
+    /// difference for the first element.
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields the consecutive differences
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let odometer = vec![1200, 1250, 1310, 1310];
+    /// let trips: Vec<i32> = odometer.into_iter().diffs().collect();
+    /// assert_eq!(trips, vec![50, 60, 0]);
+    /// ```
+    fn diffs(self) -> Diffs<Self>
+    where
+        Self: Sized,
+        Self::Item: std::ops::Sub<Output = Self::Item> + Clone,
+    {
+        Diffs::new(self, None)
+    }
+
+    /// Returns an iterator over the differences between consecutive numeric elements, using
+    /// `initial` as the value preceding the first element.
+    ///
+    /// Every element yields a difference, so `n` elements produce `n` differences.
+    ///
+    /// # Arguments
+    ///
+    /// * `initial` - The value subtracted from the first element
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields the consecutive differences
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let levels = vec![1.5, 2.0, 1.0];
+    /// let changes: Vec<f64> = levels.into_iter().diffs_from(0.0).collect();
+    /// assert_eq!(changes, vec![1.5, 0.5, -1.0]);
+    /// ```
+    fn diffs_from(self, initial: Self::Item) -> Diffs<Self>
+    where
+        Self: Sized,
+        Self::Item: std::ops::Sub<Output = Self::Item> + Clone,
+    {
+        Diffs::new(self, Some(initial))
+    }
+
+    /// Returns an iterator that delta-encodes a stream of integers.
+    ///
+    /// The first value is yielded as is, followed by the difference between each element and
+    /// its predecessor. Differences are expressed in the signed type of the same width, so
+    /// decreasing runs of unsigned values encode as negative deltas. Arithmetic wraps, which
+    /// keeps every input exactly recoverable with `delta_decode`.
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields the first value followed by successive differences
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let ids = vec![1000u32, 1003, 1004, 1001];
+    /// let encoded: Vec<i32> = ids.iter().copied().delta_encode().collect();
+    /// assert_eq!(encoded, vec![1000, 3, 1, -3]);
+    ///
+    /// let decoded: Vec<u32> = encoded.into_iter().delta_decode().collect();
+    /// assert_eq!(decoded, ids);
+    /// ```
+    fn delta_encode(self) -> DeltaEncode<Self>
+    where
+        Self: Sized,
+        Self::Item: IntegerLike,
+    {
+        DeltaEncode::new(self)
+    }
+
+    /// Returns an iterator that reverses `delta_encode`, rebuilding the original integers from
+    /// a first value followed by successive differences.
+    ///
+    /// The target type `T` is the integer type whose signed counterpart matches the deltas,
+    /// and is usually inferred from the surrounding context.
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields the running prefix sums of the deltas as `T`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let deltas = vec![5i64, 2, 2, 10];
+    /// let ids: Vec<u64> = deltas.into_iter().delta_decode().collect();
+    /// assert_eq!(ids, vec![5, 7, 9, 19]);
+    /// ```
+    fn delta_decode<T>(self) -> DeltaDecode<Self, T>
+    where
+        Self: Sized,
+        T: IntegerLike<Signed = Self::Item>,
+    {
+        DeltaDecode::new(self)
+    }
+
+    /// Returns an iterator over the finite-difference slopes of a series of `(x, y)` points.
+    ///
+    /// For each pair of consecutive points yields `(y1 - y0) / (x1 - x0)`, so `n` points produce
+    /// `n - 1` slopes. Spacing may be irregular; each slope uses its own interval. Points with
+    /// equal `x` produce an infinite or NaN slope, following `f64` division.
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields the slope between each pair of consecutive points
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let samples = vec![(0.0, 0.0), (1.0, 2.0), (3.0, 3.0)];
+    /// let slopes: Vec<f64> = samples.into_iter().derivative().collect();
+    /// assert_eq!(slopes, vec![2.0, 0.5]);
+    /// ```
+    fn derivative<X, Y>(self) -> Derivative<Self>
+    where
+        Self: Sized + Iterator<Item = (X, Y)>,
+        X: Into<f64>,
+        Y: Into<f64>,
+    {
+        Derivative::new(self)
+    }
+
+    /// Computes the integral of a series of `(x, y)` points using the trapezoidal rule.
+    ///
+    /// Each pair of consecutive points contributes `(x1 - x0) * (y0 + y1) / 2`, summed with
+    /// compensated summation. Spacing may be irregular, and segments where `x` decreases
+    /// contribute negative area.
+    ///
+    /// # Returns
+    ///
+    /// The approximate area under the curve, or `0.0` if there are fewer than two points
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let speed = vec![(0.0, 0.0), (2.0, 10.0), (5.0, 10.0)];
+    /// assert_eq!(speed.into_iter().integrate_trapezoid(), 40.0);
+    /// ```
+    fn integrate_trapezoid<X, Y>(self) -> f64
+    where
+        Self: Sized + Iterator<Item = (X, Y)>,
+        X: Into<f64>,
+        Y: Into<f64>,
+    {
+        self.cumulative_trapezoid().last().unwrap_or(0.0)
+    }
+
+    /// Returns an iterator over the running trapezoidal integral of a series of `(x, y)` points.
+    ///
+    /// Yields the accumulated area up to each point, starting with `0.0` at the first point, so
+    /// the last value equals `integrate_trapezoid`.
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields the cumulative area at each point
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let flow = vec![(0, 1.0), (1, 3.0), (3, 3.0)];
+    /// let volume: Vec<f64> = flow.into_iter().cumulative_trapezoid().collect();
+    /// assert_eq!(volume, vec![0.0, 2.0, 8.0]);
+    /// ```
+    fn cumulative_trapezoid<X, Y>(self) -> CumulativeTrapezoid<Self>
+    where
+        Self: Sized + Iterator<Item = (X, Y)>,
+        X: Into<f64>,
+        Y: Into<f64>,
+    {
+        CumulativeTrapezoid::new(self)
+    }
+
+    /// Returns an iterator that reduces every `size` consecutive elements to one value.
+    ///
+    /// Elements are grouped in order into chunks of `size`, and each chunk is combined with
+    /// the chosen `Aggregate`: its mean, minimum, maximum, first or last value. The final
+    /// chunk may be shorter. `Min` and `Max` ignore NaN values unless the whole chunk is NaN.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The number of elements per group
+    /// * `aggregate` - How each group is reduced to a single value
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields one `f64` per group
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::{IterExtra, prelude::Aggregate};
+    ///
+    /// let signal = vec![1.0, 3.0, 2.0, 8.0, 4.0];
+    /// let means: Vec<f64> = signal.iter().copied().downsample(2, Aggregate::Mean).collect();
+    /// assert_eq!(means, vec![2.0, 5.0, 4.0]);
+    ///
+    /// let peaks: Vec<f64> = signal.into_iter().downsample(2, Aggregate::Max).collect();
+    /// assert_eq!(peaks, vec![3.0, 8.0, 4.0]);
+    /// ```
+    fn downsample(
+        self,
+        size: usize,
+        aggregate: Aggregate,
+    ) -> Downsample<Self, impl FnMut(&[Self::Item]) -> f64>
+    where
+        Self: Sized,
+        Self::Item: Clone + Into<f64>,
+    {
+        Downsample::new(self, size, move |group: &[Self::Item]| {
+            aggregate_group(aggregate, group)
+        })
+    }
+
+    /// Returns an iterator that reduces every `size` consecutive elements with a custom
+    /// function.
+    ///
+    /// Like `downsample`, but each group is passed to `aggregate_fn` as a slice, which allows
+    /// any reduction and any output type. The final group may be shorter than `size`.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The number of elements per group
+    /// * `aggregate_fn` - A function that reduces a non-empty group to a single value
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields one value per group
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let samples = vec![4, 9, 1, 7, 7, 2, 5];
+    /// let ranges: Vec<(i32, i32)> = samples
+    ///     .into_iter()
+    ///     .downsample_with(3, |group| {
+    ///         (*group.iter().min().unwrap(), *group.iter().max().unwrap())
+    ///     })
+    ///     .collect();
+    /// assert_eq!(ranges, vec![(1, 9), (2, 7), (5, 5)]);
+    /// ```
+    fn downsample_with<B, F>(self, size: usize, aggregate_fn: F) -> Downsample<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(&[Self::Item]) -> B,
+    {
+        Downsample::new(self, size, aggregate_fn)
+    }
+
+    /// Returns an iterator that fills interior `None` gaps by linear interpolation.
+    ///
+    /// Elements are treated as evenly spaced samples. Each run of `None` values between two
+    /// known values is replaced by points on the straight line joining them. Leading and
+    /// trailing `None` values have only one neighbor and are yielded unchanged. Runs of
+    /// missing values are buffered until the next known value arrives.
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields `Option<f64>` with every interior gap filled
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let samples = vec![None, Some(1.0), None, None, Some(4.0), None];
+    /// let filled: Vec<Option<f64>> = samples.into_iter().interpolate_linear().collect();
+    /// assert_eq!(filled, vec![None, Some(1.0), Some(2.0), Some(3.0), Some(4.0), None]);
+    /// ```
+    fn interpolate_linear<T>(self) -> InterpolateLinear<Self>
+    where
+        Self: Sized + Iterator<Item = Option<T>>,
+        T: Into<f64>,
+    {
+        InterpolateLinear::new(self)
+    }
+
+    /// Returns an iterator that fills interior missing `y` values of `(x, Option<y>)` points by
+    /// linear interpolation along `x`.
+    ///
+    /// Like `interpolate_linear`, but each gap is interpolated at its own `x` position, so
+    /// irregular spacing is respected. Leading and trailing missing values stay `None`.
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields `(x, Option<y>)` points with every interior gap filled
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let readings = vec![(0.0, Some(10.0)), (1.0, None), (4.0, Some(20.0))];
+    /// let filled: Vec<_> = readings.into_iter().interpolate_linear_xy().collect();
+    /// assert_eq!(filled, vec![(0.0, Some(10.0)), (1.0, Some(12.5)), (4.0, Some(20.0))]);
+    /// ```
+    fn interpolate_linear_xy<X, Y>(self) -> InterpolateLinearXy<Self>
+    where
+        Self: Sized + Iterator<Item = (X, Option<Y>)>,
+        X: Into<f64>,
+        Y: Into<f64>,
+    {
+        InterpolateLinearXy::new(self)
+    }
+
+    /// Returns an iterator that replaces each `None` with the most recent `Some` value.
+    ///
+    /// Leading `None` values have nothing to carry forward and are yielded unchanged.
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields `Option<T>` with gaps filled from the left
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let status = vec![None, Some("up"), None, Some("down"), None];
+    /// let filled: Vec<Option<&str>> = status.into_iter().forward_fill().collect();
+    /// assert_eq!(filled, vec![None, Some("up"), Some("up"), Some("down"), Some("down")]);
+    /// ```
+    fn forward_fill<T>(self) -> ForwardFill<Self, T>
+    where
+        Self: Sized + Iterator<Item = Option<T>>,
+        T: Clone,
+    {
+        ForwardFill::new(self)
+    }
+
+    /// Returns an iterator that replaces each `None` with the next `Some` value.
+    ///
+    /// The counterpart of `forward_fill`. Runs of `None` are consumed until the next known
+    /// value arrives, so only a count is buffered, never the elements themselves. Trailing
+    /// `None` values have nothing to carry backward and are yielded unchanged.
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields `Option<T>` with gaps filled from the right
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let readings = vec![None, None, Some(3), None, Some(5), None];
+    /// let filled: Vec<Option<i32>> = readings.into_iter().backward_fill().collect();
+    /// assert_eq!(filled, vec![Some(3), Some(3), Some(3), Some(5), Some(5), None]);
+    /// ```
+    fn backward_fill<T>(self) -> BackwardFill<Self, T>
+    where
+        Self: Sized + Iterator<Item = Option<T>>,
+        T: Clone,
+    {
+        BackwardFill::new(self)
+    }
+
+    /// Returns the positions of the local maxima of a numeric series.
+    ///
+    /// A peak is an element strictly greater than both of its neighbors; for a flat plateau the
+    /// position of its first element is reported. The first and last elements are never peaks,
+    /// and NaN values never compare greater, so they are neither peaks nor neighbors that
+    /// confirm one. Peaks can be filtered by `PeakOptions`:
+    ///
+    /// * `min_prominence` drops peaks that rise less than this above the higher of the lowest
+    ///   points separating them from a taller element on either side.
+    /// * `min_distance` keeps only the tallest peak among any peaks closer than this many
+    ///   positions to each other.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Prominence and distance filters; `PeakOptions::default()` keeps every peak
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<usize>` of peak positions in ascending order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::{IterExtra, prelude::PeakOptions};
+    ///
+    /// let signal = vec![0.0, 5.0, 4.8, 5.1, 0.0, 2.0, 1.0];
+    /// assert_eq!(signal.iter().copied().peaks(PeakOptions::default()), vec![1, 3, 5]);
+    ///
+    /// let prominent = PeakOptions { min_prominence: 1.0, ..PeakOptions::default() };
+    /// assert_eq!(signal.iter().copied().peaks(prominent), vec![3, 5]);
+    ///
+    /// let spaced = PeakOptions { min_distance: 3, ..PeakOptions::default() };
+    /// assert_eq!(signal.into_iter().peaks(spaced), vec![3]);
+    /// ```
+    fn peaks(self, options: PeakOptions) -> Vec<usize>
+    where
+        Self: Sized,
+        Self::Item: Into<f64>,
+    {
+        let values = self.map(Into::into).collect::<Vec<f64>>();
+        find_peaks(&values, options)
+    }
+
+    /// Returns the positions of the local minima of a numeric series.
+    ///
+    /// The mirror image of `peaks`: a trough is an element strictly less than both of its
+    /// neighbors, with prominence measured as depth below the surrounding bases.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Prominence and distance filters; `PeakOptions::default()` keeps every trough
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<usize>` of trough positions in ascending order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::{IterExtra, prelude::PeakOptions};
+    ///
+    /// let prices = vec![10, 7, 9, 9, 4, 8];
+    /// assert_eq!(prices.into_iter().troughs(PeakOptions::default()), vec![1, 4]);
+    /// ```
+    fn troughs(self, options: PeakOptions) -> Vec<usize>
+    where
+        Self: Sized,
+        Self::Item: Into<f64>,
+    {
+        let values = self.map(|item| -item.into()).collect::<Vec<f64>>();
+        find_peaks(&values, options)
+    }
+
+    /// Returns an iterator that splits the elements into maximal monotone runs by key.
+    ///
+    /// Each run is either non-decreasing (`Trend::Rising`) or non-increasing
+    /// (`Trend::Falling`) and extends as long as possible; equal keys continue a run in either
+    /// direction. A run whose keys are all equal, including a single-element run, is
+    /// `Trend::Flat`. The element that breaks a run starts the next one, so every element
+    /// belongs to exactly one run. A key that is incomparable with its predecessor (like NaN)
+    /// also starts a new run.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_fn` - A function that extracts a key from each element for comparison
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields `(Trend, Vec<Self::Item>)` for each run
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::{IterExtra, prelude::Trend};
+    ///
+    /// let prices = vec![1.0, 2.0, 2.0, 5.0, 3.0, 1.0, 4.0];
+    /// let runs: Vec<(Trend, Vec<f64>)> = prices
+    ///     .into_iter()
+    ///     .monotone_runs_by_partial_key(|&x| x)
+    ///     .collect();
+    /// assert_eq!(
+    ///     runs,
+    ///     vec![
+    ///         (Trend::Rising, vec![1.0, 2.0, 2.0, 5.0]),
+    ///         (Trend::Falling, vec![3.0, 1.0]),
+    ///         (Trend::Flat, vec![4.0]),
+    ///     ]
+    /// );
+    /// ```
+    fn monotone_runs_by_partial_key<K, F>(self, key_fn: F) -> MonotoneRunsByPartialKey<Self, K, F>
+    where
+        Self: Sized,
+        K: PartialOrd,
+        F: FnMut(&Self::Item) -> K,
+    {
+        MonotoneRunsByPartialKey::new(self, key_fn)
+    }
+
+    /// Finds the longest run of consecutive elements that share the same key.
+    ///
+    /// If several runs have the same length, the first one wins.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_fn` - A function that extracts a key from each element
+    ///
+    /// # Returns
+    ///
+    /// * `Some((start, len))` - The position of the first element of the longest run and its length
+    /// * `None` - If the iterator is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let outcomes = "WWLWWWLLW".chars();
+    /// assert_eq!(outcomes.longest_run_by_key(|&c| c), Some((3, 3)));
+    /// ```
+    fn longest_run_by_key<K, F>(self, mut key_fn: F) -> Option<(usize, usize)>
+    where
+        Self: Sized,
+        K: PartialEq,
+        F: FnMut(&Self::Item) -> K,
+    {
+        let mut best: Option<(usize, usize)> = None;
+        let mut current: Option<(K, usize, usize)> = None;
+        for (index, item) in self.enumerate() {
+            let key = key_fn(&item);
+            current = match current {
+                Some((run_key, start, len)) if run_key == key => Some((run_key, start, len + 1)),
+                _ => Some((key, index, 1)),
+            };
+            if let Some((_, start, len)) = current
+                && best.is_none_or(|(_, best_len)| len > best_len)
+            {
+                best = Some((start, len));
+            }
+        }
+        best
+    }
+
+    /// Finds the longest run of consecutive elements that satisfy a predicate.
+    ///
+    /// If several runs have the same length, the first one wins.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - A function that decides whether an element belongs to a run
+    ///
+    /// # Returns
+    ///
+    /// * `Some((start, len))` - The position of the first element of the longest run and its length
+    /// * `None` - If no element satisfies the predicate
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let daily_errors = vec![0, 3, 0, 0, 0, 1, 0];
+    /// assert_eq!(daily_errors.iter().longest_run_by(|&&n| n == 0), Some((2, 3)));
+    /// ```
+    fn longest_run_by<F>(self, mut predicate: F) -> Option<(usize, usize)>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> bool,
+    {
+        let mut best: Option<(usize, usize)> = None;
+        let mut current: Option<(usize, usize)> = None;
+        for (index, item) in self.enumerate() {
+            current = match (predicate(&item), current) {
+                (false, _) => None,
+                (true, Some((start, len))) => Some((start, len + 1)),
+                (true, None) => Some((index, 1)),
+            };
+            if let Some((start, len)) = current
+                && best.is_none_or(|(_, best_len)| len > best_len)
+            {
+                best = Some((start, len));
+            }
+        }
+        best
+    }
+
+    /// Returns an iterator over the positions where a numeric series crosses a threshold.
+    ///
+    /// The series starts on the side of `threshold` given by its first value, where a value
+    /// equal to the threshold counts as above. An upward crossing is reported at the first
+    /// position at or above the threshold after being below it, and a downward crossing at
+    /// the first position below it after being above. NaN values are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold` - The level whose crossings are reported
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields `(index, Crossing)` for each crossing
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::{IterExtra, prelude::Crossing};
+    ///
+    /// let temperature = vec![18.0, 21.5, 22.0, 19.0, 20.0];
+    /// let crossings: Vec<(usize, Crossing)> = temperature.into_iter().crossings(20.0).collect();
+    /// assert_eq!(
+    ///     crossings,
+    ///     vec![(1, Crossing::Upward), (3, Crossing::Downward), (4, Crossing::Upward)]
+    /// );
+    /// ```
+    fn crossings(self, threshold: f64) -> Crossings<Self>
+    where
+        Self: Sized,
+        Self::Item: Into<f64>,
+    {
+        Crossings::new(self, threshold, 0.0)
+    }
+
+    /// Returns an iterator over the positions where a numeric series crosses a threshold,
+    /// ignoring oscillations inside a dead band around it.
+    ///
+    /// Like `crossings`, but an upward crossing requires reaching `threshold + hysteresis`
+    /// and a downward crossing requires falling below `threshold - hysteresis`, so noise
+    /// around the threshold does not produce a burst of alternating crossings.
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold` - The level whose crossings are reported
+    /// * `hysteresis` - Half the width of the dead band around the threshold
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields `(index, Crossing)` for each crossing
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hysteresis` is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::{IterExtra, prelude::Crossing};
+    ///
+    /// let noisy = vec![9.0, 10.2, 9.9, 10.1, 11.5, 9.7, 8.0];
+    /// let crossings: Vec<(usize, Crossing)> = noisy
+    ///     .into_iter()
+    ///     .crossings_with_hysteresis(10.0, 1.0)
+    ///     .collect();
+    /// assert_eq!(crossings, vec![(4, Crossing::Upward), (6, Crossing::Downward)]);
+    /// ```
+    fn crossings_with_hysteresis(self, threshold: f64, hysteresis: f64) -> Crossings<Self>
+    where
+        Self: Sized,
+        Self::Item: Into<f64>,
+    {
+        Crossings::new(self, threshold, hysteresis)
+    }
+
+    /// Returns an iterator that groups elements into fixed-width buckets of a numeric key.
+    ///
+    /// Each element falls into the bucket starting at the largest multiple of `width` that is
+    /// not greater than its key, so negative keys are bucketed consistently. The input is
+    /// expected to be sorted by key: consecutive elements in the same bucket are grouped, and
+    /// a new group starts whenever the bucket changes. Only one bucket is held in memory at a
+    /// time, and empty buckets are not yielded.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_fn` - A function that extracts a numeric key, such as a timestamp, from each element
+    /// * `width` - The width of each bucket, in key units
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields `(bucket_start, Vec<Self::Item>)` for each non-empty bucket
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let events = vec![(1_000, "start"), (1_030, "ping"), (1_065, "ping"), (1_200, "stop")];
+    /// let per_minute: Vec<(i64, Vec<&str>)> = events
+    ///     .into_iter()
+    ///     .bucket_by(|&(ts, _)| ts, 60)
+    ///     .map(|(start, group)| (start, group.into_iter().map(|(_, e)| e).collect()))
+    ///     .collect();
+    /// assert_eq!(
+    ///     per_minute,
+    ///     vec![
+    ///         (960, vec!["start"]),
+    ///         (1_020, vec!["ping", "ping"]),
+    ///         (1_200, vec!["stop"]),
+    ///     ]
+    /// );
+    /// ```
+    fn bucket_by<K, F>(self, key_fn: F, width: K) -> BucketBy<Self, K, F>
+    where
+        Self: Sized,
+        K: Copy
+            + PartialOrd
+            + Default
+            + std::ops::Add<Output = K>
+            + std::ops::Sub<Output = K>
+            + std::ops::Rem<Output = K>,
+        F: FnMut(&Self::Item) -> K,
+    {
+        BucketBy::new(self, key_fn, width)
+    }
+
+    /// Collects exactly `N` elements into a fixed-size array.
+    ///
+    /// Stops at the first element past `N`, so a too-long iterator is not drained.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(array)` - If the iterator yielded exactly `N` elements
+    /// * `Err(CollectArrayError::TooFew { .. })` - If it ended early, with the number of elements
+    /// * `Err(CollectArrayError::TooMany { .. })` - If it yielded more than `N` elements
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::{IterExtra, prelude::CollectArrayError};
+    ///
+    /// let [r, g, b] = "255,128,0".split(',').collect_array::<3>().unwrap();
+    /// assert_eq!((r, g, b), ("255", "128", "0"));
+    ///
+    /// let short = "1,2".split(',').collect_array::<3>();
+    /// assert_eq!(short, Err(CollectArrayError::TooFew { expected: 3, actual: 2 }));
+    /// ```
+    fn collect_array<const N: usize>(mut self) -> Result<[Self::Item; N], CollectArrayError>
+    where
+        Self: Sized,
+    {
+        let mut filled = 0;
+        let slots: [Option<Self::Item>; N] = std::array::from_fn(|index| {
+            // Stop pulling after the first `None`, even if the iterator is not fused.
+            let item = if filled == index { self.next() } else { None };
+            filled += usize::from(item.is_some());
+            item
+        });
+        if filled < N {
+            return Err(CollectArrayError::TooFew {
+                expected: N,
+                actual: filled,
+            });
+        }
+        if self.next().is_some() {
+            return Err(CollectArrayError::TooMany {
+                expected: N,
+                at_least: N + 1,
+            });
+        }
+        Ok(slots.map(|slot| slot.expect("all N slots were filled")))
+    }
+
+    /// Collects exactly `n` elements into a `Vec`, failing if the iterator yields a different
+    /// number.
+    ///
+    /// At most `n + 1` elements are consumed, so an over-long or infinite iterator is rejected
+    /// as soon as the first extra element appears.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The required number of elements
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(vec)` - If the iterator yielded exactly `n` elements
+    /// * `Err(LengthError)` - With the expected and received counts otherwise
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::{IterExtra, prelude::LengthError};
+    ///
+    /// let row = "alice,30,paris".split(',').collect_exact(3);
+    /// assert_eq!(row, Ok(vec!["alice", "30", "paris"]));
+    ///
+    /// let bad_row = "bob,41".split(',').collect_exact(3);
+    /// assert_eq!(bad_row, Err(LengthError { expected: 3, actual: 2 }));
+    /// ```
+    fn collect_exact(mut self, n: usize) -> Result<Vec<Self::Item>, LengthError>
+    where
+        Self: Sized,
+    {
+        let items = self.by_ref().take(n).collect::<Vec<_>>();
+        let actual = items.len() + usize::from(items.len() == n && self.next().is_some());
+        if actual == n {
+            Ok(items)
+        } else {
+            Err(LengthError {
+                expected: n,
+                actual,
+            })
+        }
+    }
+
+    /// Collects the elements into a tuple if there are exactly as many as the tuple has fields.
+    ///
+    /// Supported for tuples of two to four elements of the iterator's item type. At most one
+    /// element past the tuple's arity is consumed.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(tuple)` - If the iterator yielded exactly the tuple's number of elements
+    /// * `None` - If it yielded fewer or more
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let (key, value) = "timeout=30".split('=').collect_tuple().unwrap();
+    /// assert_eq!((key, value), ("timeout", "30"));
+    ///
+    /// assert_eq!("a=b=c".split('=').collect_tuple::<(&str, &str)>(), None);
+    /// ```
+    fn collect_tuple<T>(mut self) -> Option<T>
+    where
+        Self: Sized,
+        T: TupleCollect<Item = Self::Item>,
+    {
+        let tuple = T::take_from(&mut self)?;
+        self.next().is_none().then_some(tuple)
+    }
+
+    /// Collects the elements into a `SmallVec` that stores up to `N` of them inline.
+    ///
+    /// Only spills to the heap when more than `N` elements are yielded, which avoids an
+    /// allocation on hot paths that usually produce a handful of items.
+    ///
+    /// Requires the `smallvec` feature.
+    ///
+    /// # Returns
+    ///
+    /// A `SmallVec<[Self::Item; N]>` with all elements
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let digits = "2024".chars().filter_map(|c| c.to_digit(10)).collect_smallvec::<4>();
+    /// assert_eq!(digits.as_slice(), &[2, 0, 2, 4]);
+    /// assert!(!digits.spilled());
+    /// ```
+    #[cfg(feature = "smallvec")]
+    fn collect_smallvec<const N: usize>(self) -> smallvec::SmallVec<[Self::Item; N]>
+    where
+        Self: Sized,
+    {
+        self.collect()
+    }
+
+    /// Collects the elements into a `SmallVec` with inline capacity `N`, returning `None` if
+    /// there are none.
+    ///
+    /// The `SmallVec` counterpart of `collect_some_vec`. Requires the `smallvec` feature.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(small_vec)` - If the iterator yielded at least one element
+    /// * `None` - If the iterator was empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let evens = (1..6).filter(|n| n % 2 == 0).collect_some_smallvec::<8>();
+    /// assert_eq!(evens.as_deref(), Some(&[2, 4][..]));
+    ///
+    /// assert!((1..6).filter(|n| *n > 10).collect_some_smallvec::<8>().is_none());
+    /// ```
+    #[cfg(feature = "smallvec")]
+    fn collect_some_smallvec<const N: usize>(self) -> Option<smallvec::SmallVec<[Self::Item; N]>>
+    where
+        Self: Sized,
+    {
+        self.collect_some()
+    }
+
+    /// Collects the elements into a stack-allocated `ArrayVec` with capacity `N`.
+    ///
+    /// Stops at the first element that does not fit, so a too-long iterator is not drained.
+    ///
+    /// Requires the `arrayvec` feature.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(array_vec)` - If the iterator yielded at most `N` elements
+    /// * `Err(CapacityError)` - Holding the first element that exceeded the capacity
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let header = [0xCAu8, 0xFE, 0xBA, 0xBE].into_iter().collect_arrayvec::<8>().unwrap();
+    /// assert_eq!(header.len(), 4);
+    ///
+    /// let overflow = (0..10).collect_arrayvec::<3>();
+    /// assert_eq!(overflow.unwrap_err().element(), 3);
+    /// ```
+    #[cfg(feature = "arrayvec")]
+    fn collect_arrayvec<const N: usize>(
+        self,
+    ) -> Result<arrayvec::ArrayVec<Self::Item, N>, arrayvec::CapacityError<Self::Item>>
+    where
+        Self: Sized,
+    {
+        let mut items = arrayvec::ArrayVec::new();
+        for item in self {
+            items.try_push(item)?;
+        }
+        Ok(items)
+    }
+
+    /// Collects `(key, value)` pairs into a `HashMap`, resolving duplicate keys with an explicit
+    /// policy.
+    ///
+    /// Plain `collect::<HashMap<_, _>>()` silently keeps the last value for a repeated key.
+    /// Here the caller chooses to keep either the first or the last value; use
+    /// `collect_hashmap_with` to merge the values instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - How to resolve a key that appears more than once
+    ///
+    /// # Returns
+    ///
+    /// A `HashMap` with one entry per distinct key
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::{IterExtra, prelude::DuplicateKeyPolicy};
+    ///
+    /// let sales = vec![("north", 10), ("south", 4), ("north", 7)];
+    ///
+    /// let first = sales.iter().copied().collect_hashmap(DuplicateKeyPolicy::KeepFirst);
+    /// assert_eq!(first["north"], 10);
+    ///
+    /// let last = sales.into_iter().collect_hashmap(DuplicateKeyPolicy::KeepLast);
+    /// assert_eq!(last["north"], 7);
+    /// ```
+    fn collect_hashmap<K, V>(self, policy: DuplicateKeyPolicy) -> std::collections::HashMap<K, V>
+    where
+        Self: Sized + Iterator<Item = (K, V)>,
+        K: Eq + std::hash::Hash,
+    {
+        match policy {
+            DuplicateKeyPolicy::KeepFirst => self.collect_hashmap_with(|_, _| {}),
+            DuplicateKeyPolicy::KeepLast => self.collect_hashmap_with(|stored, value| {
+                *stored = value;
+            }),
+        }
+    }
+
+    /// Collects `(key, value)` pairs into a `HashMap`, merging the values of duplicate keys.
+    ///
+    /// The first value for a key is stored as is; every later value for the same key is merged
+    /// into the stored one in place, in iteration order.
+    ///
+    /// # Arguments
+    ///
+    /// * `merge_fn` - A function that merges a later value into the stored value
+    ///
+    /// # Returns
+    ///
+    /// A `HashMap` with one entry per distinct key
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let sales = vec![("north", 10), ("south", 4), ("north", 7)];
+    /// let totals = sales.into_iter().collect_hashmap_with(|total, amount| *total += amount);
+    /// assert_eq!(totals["north"], 17);
+    /// assert_eq!(totals["south"], 4);
+    /// ```
+    fn collect_hashmap_with<K, V, F>(self, mut merge_fn: F) -> std::collections::HashMap<K, V>
+    where
+        Self: Sized + Iterator<Item = (K, V)>,
+        K: Eq + std::hash::Hash,
+        F: FnMut(&mut V, V),
+    {
+        use std::collections::hash_map::Entry;
+
+        let mut map = std::collections::HashMap::new();
+        for (key, value) in self {
+            match map.entry(key) {
+                Entry::Vacant(entry) => {
+                    entry.insert(value);
+                }
+                Entry::Occupied(mut entry) => merge_fn(entry.get_mut(), value),
+            }
+        }
+        map
+    }
+
+    /// Collects `(key, value)` pairs into a `HashMap`, failing on the first repeated key.
+    ///
+    /// Useful for building lookup tables where a duplicate key means the input is corrupt.
+    /// Iteration stops at the first duplicate.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(map)` - If every key is distinct
+    /// * `Err(DuplicateKeyError)` - With the repeated key and the position of its second occurrence
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::{IterExtra, prelude::DuplicateKeyError};
+    ///
+    /// let ports = vec![("http", 80), ("https", 443)];
+    /// let table = ports.into_iter().try_collect_unique_hashmap().unwrap();
+    /// assert_eq!(table["https"], 443);
+    ///
+    /// let corrupt = vec![("http", 80), ("ssh", 22), ("http", 8080)];
+    /// let err = corrupt.into_iter().try_collect_unique_hashmap().unwrap_err();
+    /// assert_eq!(err, DuplicateKeyError { key: "http", index: 2 });
+    /// ```
+    fn try_collect_unique_hashmap<K, V>(
+        self,
+    ) -> Result<std::collections::HashMap<K, V>, DuplicateKeyError<K>>
+    where
+        Self: Sized + Iterator<Item = (K, V)>,
+        K: Eq + std::hash::Hash,
+    {
+        use std::collections::hash_map::Entry;
+
+        let mut map = std::collections::HashMap::new();
+        for (index, (key, value)) in self.enumerate() {
+            match map.entry(key) {
+                Entry::Vacant(entry) => {
+                    entry.insert(value);
+                }
+                Entry::Occupied(entry) => {
+                    let (key, _) = entry.remove_entry();
+                    return Err(DuplicateKeyError { key, index });
+                }
+            }
+        }
+        Ok(map)
+    }
+
+    /// Groups `(key, value)` pairs into a `HashMap` from each key to all of its values.
+    ///
+    /// Values are kept in iteration order within each key.
+    ///
+    /// # Returns
+    ///
+    /// A `HashMap<K, Vec<V>>` with one entry per distinct key
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let edges = vec![("a", "b"), ("b", "c"), ("a", "c")];
+    /// let adjacency = edges.into_iter().collect_multimap();
+    /// assert_eq!(adjacency["a"], vec!["b", "c"]);
+    /// assert_eq!(adjacency["b"], vec!["c"]);
+    /// ```
+    fn collect_multimap<K, V>(self) -> std::collections::HashMap<K, Vec<V>>
+    where
+        Self: Sized + Iterator<Item = (K, V)>,
+        K: Eq + std::hash::Hash,
+    {
+        let mut map = std::collections::HashMap::<K, Vec<V>>::new();
+        for (key, value) in self {
+            map.entry(key).or_default().push(value);
+        }
+        map
+    }
+
+    /// Groups `(key, value)` pairs into a `BTreeMap` from each key to all of its values.
+    ///
+    /// The ordered counterpart of `collect_multimap`: keys iterate in sorted order, and values
+    /// are kept in iteration order within each key.
+    ///
+    /// # Returns
+    ///
+    /// A `BTreeMap<K, Vec<V>>` with one entry per distinct key
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let scores = vec![(3, "carol"), (1, "alice"), (3, "dave")];
+    /// let by_rank = scores.into_iter().collect_btree_multimap();
+    /// let ranks: Vec<_> = by_rank.into_iter().collect();
+    /// assert_eq!(ranks, vec![(1, vec!["alice"]), (3, vec!["carol", "dave"])]);
+    /// ```
+    fn collect_btree_multimap<K, V>(self) -> std::collections::BTreeMap<K, Vec<V>>
+    where
+        Self: Sized + Iterator<Item = (K, V)>,
+        K: Ord,
+    {
+        let mut map = std::collections::BTreeMap::<K, Vec<V>>::new();
+        for (key, value) in self {
+            map.entry(key).or_default().push(value);
+        }
+        map
+    }
+
+    /// Deduplicates the elements while counting them, keeping first-occurrence order.
+    ///
+    /// Combines deduplication with `counts` in a single pass: each distinct element appears
+    /// once, at the position where it was first seen, together with the number of times it
+    /// occurs.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<(Self::Item, usize)>` of distinct elements and their counts, in order of first
+    /// occurrence
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let visits = vec!["home", "docs", "home", "blog", "docs", "home"];
+    /// let counted = visits.into_iter().collect_counted_set();
+    /// assert_eq!(counted, vec![("home", 3), ("docs", 2), ("blog", 1)]);
+    /// ```
+    fn collect_counted_set(self) -> Vec<(Self::Item, usize)>
+    where
+        Self: Sized,
+        Self::Item: Eq + std::hash::Hash,
+    {
+        let mut seen = std::collections::HashMap::<Self::Item, (usize, usize)>::new();
+        for item in self {
+            let next_order = seen.len();
+            seen.entry(item).or_insert((next_order, 0)).1 += 1;
+        }
+
+        let mut counted = seen.into_iter().collect::<Vec<_>>();
+        counted.sort_unstable_by_key(|(_, (order, _))| *order);
+        (counted.into_iter())
+            .map(|(item, (_, count))| (item, count))
+            .collect()
+    }
+
+    /// Splits the elements into two `Vec`s of possibly different types in a single pass.
+    ///
+    /// Unlike `Iterator::partition`, which keeps the element type, `partition_fn` maps each
+    /// element to either `Either::Left(a)` or `Either::Right(b)`, and the values are collected
+    /// into the corresponding side, in iteration order.
+    ///
+    /// # Arguments
+    ///
+    /// * `partition_fn` - A function that converts each element and picks its side
+    ///
+    /// # Returns
+    ///
+    /// A tuple `(lefts, rights)` with the values routed to each side
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::{IterExtra, prelude::Either};
+    ///
+    /// let tokens = vec!["12", "apple", "7", "pear"];
+    /// let (numbers, words): (Vec<u32>, Vec<&str>) = tokens.into_iter().partition_map(|t| {
+    ///     match t.parse::<u32>() {
+    ///         Ok(n) => Either::Left(n),
+    ///         Err(_) => Either::Right(t),
+    ///     }
+    /// });
+    /// assert_eq!(numbers, vec![12, 7]);
+    /// assert_eq!(words, vec!["apple", "pear"]);
+    /// ```
+    fn partition_map<A, B, F>(self, mut partition_fn: F) -> (Vec<A>, Vec<B>)
+    where
+        Self: Sized,
+        F: FnMut(Self::Item) -> Either<A, B>,
+    {
+        let mut lefts = Vec::new();
+        let mut rights = Vec::new();
+        for item in self {
+            match partition_fn(item) {
+                Either::Left(left) => lefts.push(left),
+                Either::Right(right) => rights.push(right),
+            }
+        }
+        (lefts, rights)
+    }
+
+    /// Distributes the elements into `n` buckets chosen by a function.
+    ///
+    /// Each element is appended to the bucket whose index `bucket_fn` returns, so elements
+    /// keep their iteration order within a bucket. Buckets that receive no elements are empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of buckets
+    /// * `bucket_fn` - A function that returns the bucket index for each element
+    ///
+    /// # Returns
+    ///
+    /// A `Vec` of `n` buckets
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bucket_fn` returns an index greater than or equal to `n`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let user_ids = vec![10u64, 11, 12, 13, 14, 15, 16];
+    /// let shards = user_ids.into_iter().partition_into_buckets(3, |&id| (id % 3) as usize);
+    /// assert_eq!(shards, vec![vec![12, 15], vec![10, 13, 16], vec![11, 14]]);
+    /// ```
+    fn partition_into_buckets<F>(self, n: usize, mut bucket_fn: F) -> Vec<Vec<Self::Item>>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        let mut buckets = (0..n).map(|_| Vec::new()).collect::<Vec<_>>();
+        for item in self {
+            let index = bucket_fn(&item);
+            assert!(
+                index < n,
+                "bucket index {index} is out of range for {n} buckets"
+            );
+            buckets[index].push(item);
+        }
+        buckets
+    }
+
+    /// Splits an iterator of `Result`s into the `Ok` values and the `Err` values.
+    ///
+    /// Every element is consumed, so all failures can be reported instead of stopping at the
+    /// first one. Both sides keep iteration order.
+    ///
+    /// # Returns
+    ///
+    /// A tuple `(oks, errs)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let inputs = vec!["3", "x", "5", "-"];
+    /// let (numbers, errors) = inputs.into_iter().map(str::parse::<i32>).partition_result();
+    /// assert_eq!(numbers, vec![3, 5]);
+    /// assert_eq!(errors.len(), 2);
+    /// ```
+    fn partition_result<T, E>(self) -> (Vec<T>, Vec<E>)
+    where
+        Self: Sized + Iterator<Item = Result<T, E>>,
+    {
+        self.partition_map(|result| match result {
+            Ok(value) => Either::Left(value),
+            Err(err) => Either::Right(err),
+        })
+    }
+
+    /// Returns an iterator over the segments separated by delimiter elements.
+    ///
+    /// Works like `slice::split` for any iterator: `n` delimiters produce `n + 1` segments,
+    /// including empty ones between adjacent delimiters and at either end, and the
+    /// delimiters themselves are dropped. Use `split_on_with` to keep delimiters or skip
+    /// empty segments.
+    ///
+    /// # Arguments
+    ///
+    /// * `is_delimiter` - A function that returns `true` for delimiter elements
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields each segment as a `Vec<Self::Item>`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let tokens = vec![1, 2, 0, 3, 0, 0, 4];
+    /// let segments: Vec<Vec<i32>> = tokens.into_iter().split_on(|&t| t == 0).collect();
+    /// assert_eq!(segments, vec![vec![1, 2], vec![3], vec![], vec![4]]);
+    /// ```
+    fn split_on<F>(self, is_delimiter: F) -> SplitOn<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> bool,
+    {
+        SplitOn::new(self, is_delimiter, SplitOptions::default())
+    }
+
+    /// Returns an iterator over the segments separated by delimiter elements, with options.
+    ///
+    /// With `keep_delimiters`, each delimiter ends the segment it closes, like
+    /// `slice::split_inclusive`, and no empty segment follows a final delimiter. With
+    /// `skip_empty`, segments without any elements are not yielded.
+    ///
+    /// # Arguments
+    ///
+    /// * `is_delimiter` - A function that returns `true` for delimiter elements
+    /// * `options` - Whether to keep delimiters and whether to skip empty segments
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields each segment as a `Vec<Self::Item>`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::{IterExtra, prelude::SplitOptions};
+    ///
+    /// let text = "one two  three ";
+    /// let words: Vec<String> = text
+    ///     .chars()
+    ///     .split_on_with(|&c| c == ' ', SplitOptions { skip_empty: true, ..Default::default() })
+    ///     .map(|w| w.into_iter().collect())
+    ///     .collect();
+    /// assert_eq!(words, vec!["one", "two", "three"]);
+    ///
+    /// let inclusive = SplitOptions { keep_delimiters: true, ..Default::default() };
+    /// let lines: Vec<String> = "a\nb\n"
+    ///     .chars()
+    ///     .split_on_with(|&c| c == '\n', inclusive)
+    ///     .map(|l| l.into_iter().collect())
+    ///     .collect();
+    /// assert_eq!(lines, vec!["a\n", "b\n"]);
+    /// ```
+    fn split_on_with<F>(self, is_delimiter: F, options: SplitOptions) -> SplitOn<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> bool,
+    {
+        SplitOn::new(self, is_delimiter, options)
+    }
+
+    /// Distributes the elements cyclically into `n` outputs.
+    ///
+    /// Element `i` goes to output `i % n`, so the outputs differ in length by at most one.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of outputs
+    ///
+    /// # Returns
+    ///
+    /// A `Vec` of `n` outputs
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let jobs = vec!["a", "b", "c", "d", "e"];
+    /// let per_worker = jobs.into_iter().distribute_round_robin(2);
+    /// assert_eq!(per_worker, vec![vec!["a", "c", "e"], vec!["b", "d"]]);
+    /// ```
+    fn distribute_round_robin(self, n: usize) -> Vec<Vec<Self::Item>>
+    where
+        Self: Sized,
+    {
+        assert!(n > 0, "number of outputs must be greater than zero");
+        self.distribute_weighted(&vec![1; n])
+    }
+
+    /// Distributes the elements cyclically into outputs, giving each output a number of
+    /// consecutive elements per round equal to its weight.
+    ///
+    /// In every round, output `i` receives the next `weights[i]` elements, in output order.
+    /// Outputs with a weight of zero receive nothing.
+    ///
+    /// # Arguments
+    ///
+    /// * `weights` - The number of elements each output receives per round
+    ///
+    /// # Returns
+    ///
+    /// A `Vec` with one output per weight
+    ///
+    /// # Panics
+    ///
+    /// Panics if every weight is zero, including when `weights` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let requests = 1..=8;
+    /// let servers = requests.distribute_weighted(&[3, 1]);
+    /// assert_eq!(servers, vec![vec![1, 2, 3, 5, 6, 7], vec![4, 8]]);
+    /// ```
+    fn distribute_weighted(self, weights: &[usize]) -> Vec<Vec<Self::Item>>
+    where
+        Self: Sized,
+    {
+        assert!(
+            weights.iter().any(|&weight| weight > 0),
+            "at least one weight must be greater than zero"
+        );
+        let mut outputs = weights.iter().map(|_| Vec::new()).collect::<Vec<_>>();
+        let mut slots = (weights.iter().enumerate())
+            .flat_map(|(output, &weight)| std::iter::repeat_n(output, weight))
+            .cycle();
+        for item in self {
+            let output = slots.next().expect("cycling non-empty slots never ends");
+            outputs[output].push(item);
+        }
+        outputs
+    }
+
+    /// Returns an iterator that filters the `Ok` values of `Result` elements, passing every
+    /// `Err` through untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - A function that decides whether an `Ok` value is kept
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields the kept `Ok` values and all errors, in order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let parsed = vec!["4", "x", "-3", "10"].into_iter().map(str::parse::<i32>);
+    /// let positive: Vec<Result<i32, _>> = parsed.filter_ok(|&n| n > 0).collect();
+    /// assert_eq!(positive.len(), 3);
+    /// assert!(positive[1].is_err());
+    /// ```
+    fn filter_ok<T, E, F>(self, predicate: F) -> FilterOk<Self, F>
+    where
+        Self: Sized + Iterator<Item = Result<T, E>>,
+        F: FnMut(&T) -> bool,
+    {
+        FilterOk::new(self, predicate)
+    }
+
+    /// Returns an iterator that maps the `Ok` values of `Result` elements, passing every
+    /// `Err` through untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `map_fn` - A function applied to each `Ok` value
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields `Result<U, E>` elements
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let readings: Vec<Result<f64, &str>> = vec![Ok(20.0), Err("offline"), Ok(37.5)];
+    /// let fahrenheit: Vec<Result<f64, &str>> = readings
+    ///     .into_iter()
+    ///     .map_ok(|c| c * 9.0 / 5.0 + 32.0)
+    ///     .collect();
+    /// assert_eq!(fahrenheit, vec![Ok(68.0), Err("offline"), Ok(99.5)]);
+    /// ```
+    fn map_ok<T, E, U, F>(self, map_fn: F) -> MapOk<Self, F>
+    where
+        Self: Sized + Iterator<Item = Result<T, E>>,
+        F: FnMut(T) -> U,
+    {
+        MapOk::new(self, map_fn)
+    }
+
+    /// Returns an iterator that converts the error of every `Err` element, passing `Ok`
+    /// values through untouched.
+    ///
+    /// Handy for unifying errors from different sources before a single
+    /// `collect::<Result<Vec<_>, _>>()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `map_fn` - A function applied to each error
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields `Result<T, E2>` elements
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let ports: Result<Vec<u16>, String> = vec!["80", "http"]
+    ///     .into_iter()
+    ///     .map(str::parse::<u16>)
+    ///     .map_err_items(|e| format!("bad port: {e}"))
+    ///     .collect();
+    /// assert_eq!(ports, Err("bad port: invalid digit found in string".to_string()));
+    /// ```
+    fn map_err_items<T, E, E2, F>(self, map_fn: F) -> MapErrItems<Self, F>
+    where
+        Self: Sized + Iterator<Item = Result<T, E>>,
+        F: FnMut(E) -> E2,
+    {
+        MapErrItems::new(self, map_fn)
+    }
+
+    /// Returns an iterator that flattens the `Ok` containers of `Result` elements, forwarding
+    /// each `Err` in its original position.
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields `Ok` for every value inside an `Ok` container and each error
+    /// as it is reached
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let batches: Vec<Result<Vec<i32>, &str>> =
+    ///     vec![Ok(vec![1, 2]), Err("timeout"), Ok(vec![3])];
+    /// let flat: Vec<Result<i32, &str>> = batches.into_iter().flatten_ok().collect();
+    /// assert_eq!(flat, vec![Ok(1), Ok(2), Err("timeout"), Ok(3)]);
+    /// ```
+    fn flatten_ok<C, E>(self) -> FlattenOk<Self, C::IntoIter>
+    where
+        Self: Sized + Iterator<Item = Result<C, E>>,
+        C: IntoIterator,
+    {
+        FlattenOk::new(self)
+    }
+
+    /// Runs `process_fn` on an iterator over the `Ok` values, stopping at the first `Err`.
+    ///
+    /// The closure sees a plain iterator of `T`, so any adapter or consumer from this crate
+    /// can be used on fallible input without collecting it first. The view ends at the first
+    /// error, and that error is returned in place of the closure's result. Elements the
+    /// closure does not consume are never inspected.
+    ///
+    /// # Arguments
+    ///
+    /// * `process_fn` - A function that consumes the iterator of `Ok` values
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(result)` - The closure's result, if no element was an `Err`
+    /// * `Err(err)` - The first error encountered
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let ok = vec!["2.5", "0.5", "4.0"].into_iter().map(str::parse::<f64>);
+    /// let max = ok.process_results(|values| values.max_by_partial_key(|&x| x));
+    /// assert_eq!(max, Ok(Some(4.0)));
+    ///
+    /// let bad = vec!["2.5", "oops", "4.0"].into_iter().map(str::parse::<f64>);
+    /// assert!(bad.process_results(|values| values.count()).is_err());
+    /// ```
+    fn process_results<T, E, R, F>(self, process_fn: F) -> Result<R, E>
+    where
+        Self: Sized + Iterator<Item = Result<T, E>>,
+        F: FnOnce(ProcessResults<'_, Self, E>) -> R,
+    {
+        let mut error = None;
+        let result = process_fn(ProcessResults {
+            iter: self,
+            error: &mut error,
+        });
+        match error {
+            Some(err) => Err(err),
+            None => Ok(result),
+        }
+    }
+
+    /// Returns an iterator that yields the inner values of `Some` elements and stops at the
+    /// first `None`.
+    ///
+    /// Once a `None` is reached, the adapter is exhausted and never polls the underlying
+    /// iterator again.
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields the values before the first `None`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let mut pending = vec![3, 2, 1];
+    /// let drained: Vec<i32> = std::iter::repeat_with(|| pending.pop()).while_some().collect();
+    /// assert_eq!(drained, vec![1, 2, 3]);
+    /// ```
+    fn while_some<T>(self) -> WhileSome<Self>
+    where
+        Self: Sized + Iterator<Item = Option<T>>,
+    {
+        WhileSome::new(self)
+    }
+
+    /// Folds the elements into an accumulator until the closure signals that it is done.
+    ///
+    /// `fold_fn` returns `FoldWhile::Continue(acc)` to keep going or `FoldWhile::Done(acc)` to
+    /// stop immediately, without consuming further elements. The result tells which of the
+    /// two ended the fold; use `into_inner` to get the accumulator either way.
+    ///
+    /// # Arguments
+    ///
+    /// * `init` - The initial accumulator value
+    /// * `fold_fn` - A function that combines the accumulator with each element
+    ///
+    /// # Returns
+    ///
+    /// * `FoldWhile::Done(acc)` - If `fold_fn` stopped the fold early
+    /// * `FoldWhile::Continue(acc)` - If every element was folded
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::{IterExtra, prelude::FoldWhile};
+    ///
+    /// let packet_sizes = vec![400, 500, 300, 200];
+    /// let batch = packet_sizes.into_iter().fold_while(0, |total, size| {
+    ///     if total + size > 1000 {
+    ///         FoldWhile::Done(total)
+    ///     } else {
+    ///         FoldWhile::Continue(total + size)
+    ///     }
+    /// });
+    /// assert_eq!(batch, FoldWhile::Done(900));
+    /// assert_eq!(batch.into_inner(), 900);
+    /// ```
+    fn fold_while<B, F>(self, init: B, mut fold_fn: F) -> FoldWhile<B>
+    where
+        Self: Sized,
+        F: FnMut(B, Self::Item) -> FoldWhile<B>,
+    {
+        let mut acc = init;
+        for item in self {
+            match fold_fn(acc, item) {
+                FoldWhile::Continue(next) => acc = next,
+                done @ FoldWhile::Done(_) => return done,
+            }
+        }
+        FoldWhile::Continue(acc)
+    }
+
+    /// Returns the last element that satisfies a predicate.
+    ///
+    /// Scans the whole iterator, so it works with any iterator. Stable Rust cannot pick a
+    /// different implementation for double-ended iterators behind a blanket trait, so those
+    /// get the separate `rfind_last`, which searches from the back and stops at the match.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - A function that decides whether an element matches
+    ///
+    /// # Returns
+    ///
+    /// * `Some(item)` - The last matching element
+    /// * `None` - If no element matches
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let log = "boot\nerror: disk\nok\nerror: net\nok";
+    /// assert_eq!(log.lines().find_last(|l| l.starts_with("error")), Some("error: net"));
+    /// ```
+    fn find_last<P>(self, mut predicate: P) -> Option<Self::Item>
+    where
+        Self: Sized,
+        P: FnMut(&Self::Item) -> bool,
+    {
+        self.filter(|item| predicate(item)).last()
+    }
+
+    /// Applies a function to the elements and returns the last non-`None` result.
+    ///
+    /// The counterpart of `Iterator::find_map` that keeps the final match instead of the
+    /// first. Like `find_last`, it scans the whole iterator; see `rfind_map_last` for
+    /// double-ended iterators.
+    ///
+    /// # Arguments
+    ///
+    /// * `map_fn` - A function that returns `Some(value)` for matching elements
+    ///
+    /// # Returns
+    ///
+    /// * `Some(value)` - The result for the last matching element
+    /// * `None` - If `map_fn` returned `None` for every element
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let args = vec!["--level=1", "-v", "--level=3"];
+    /// let level = args
+    ///     .into_iter()
+    ///     .find_map_last(|a| a.strip_prefix("--level=")?.parse::<u8>().ok());
+    /// assert_eq!(level, Some(3));
+    /// ```
+    fn find_map_last<B, F>(self, map_fn: F) -> Option<B>
+    where
+        Self: Sized,
+        F: FnMut(Self::Item) -> Option<B>,
+    {
+        self.filter_map(map_fn).last()
+    }
+
+    /// Returns the last element that satisfies a predicate, searching from the back.
+    ///
+    /// Gives the same result as `find_last`, but only visits elements from the back up to the
+    /// last match.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - A function that decides whether an element matches
+    ///
+    /// # Returns
+    ///
+    /// * `Some(item)` - The last matching element
+    /// * `None` - If no element matches
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let versions = vec!["1.0.0", "1.1.0-rc1", "1.1.0", "1.2.0-beta"];
+    /// let stable = versions.iter().rfind_last(|v| !v.contains('-'));
+    /// assert_eq!(stable, Some(&"1.1.0"));
+    /// ```
+    fn rfind_last<P>(self, mut predicate: P) -> Option<Self::Item>
+    where
+        Self: Sized + DoubleEndedIterator,
+        P: FnMut(&Self::Item) -> bool,
+    {
+        self.rev().find(|item| predicate(item))
+    }
+
+    /// Applies a function to the elements from the back and returns the first non-`None`
+    /// result, which is the last one in iteration order.
+    ///
+    /// Gives the same result as `find_map_last`, but stops at the last match instead of
+    /// scanning the whole iterator.
+    ///
+    /// # Arguments
+    ///
+    /// * `map_fn` - A function that returns `Some(value)` for matching elements
+    ///
+    /// # Returns
+    ///
+    /// * `Some(value)` - The result for the last matching element
+    /// * `None` - If `map_fn` returned `None` for every element
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let args = vec!["--level=1", "-v", "--level=3"];
+    /// let level = args
+    ///     .into_iter()
+    ///     .rfind_map_last(|a| a.strip_prefix("--level=")?.parse::<u8>().ok());
+    /// assert_eq!(level, Some(3));
+    /// ```
+    fn rfind_map_last<B, F>(self, map_fn: F) -> Option<B>
+    where
+        Self: Sized + DoubleEndedIterator,
+        F: FnMut(Self::Item) -> Option<B>,
+    {
+        self.rev().find_map(map_fn)
+    }
+
+    /// Returns the only element of the iterator, or an error describing why there is not
+    /// exactly one.
+    ///
+    /// At most two elements are consumed. If there are more than one, the error carries the
+    /// first two so the conflict can be reported.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(item)` - If the iterator yielded exactly one element
+    /// * `Err(ExactlyOneError::Empty)` - If it yielded none
+    /// * `Err(ExactlyOneError::MoreThanOne { first, second })` - If it yielded two or more
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::{IterExtra, prelude::ExactlyOneError};
+    ///
+    /// let config = vec![("port", "8080"), ("host", "localhost"), ("port", "9090")];
+    ///
+    /// let host = config.iter().filter(|(k, _)| *k == "host").exactly_one();
+    /// assert_eq!(host, Ok(&("host", "localhost")));
+    ///
+    /// let port = config.iter().filter(|(k, _)| *k == "port").exactly_one();
+    /// assert!(matches!(port, Err(ExactlyOneError::MoreThanOne { .. })));
+    /// ```
+    fn exactly_one(mut self) -> Result<Self::Item, ExactlyOneError<Self::Item>>
+    where
+        Self: Sized,
+    {
+        let first = self.next().ok_or(ExactlyOneError::Empty)?;
+        match self.next() {
+            None => Ok(first),
+            Some(second) => Err(ExactlyOneError::MoreThanOne { first, second }),
+        }
+    }
+
+    /// Returns the only element of the iterator, if any, or an error if there are several.
+    ///
+    /// Distinguishes the empty, single and multiple cases while consuming at most two
+    /// elements. The error carries the first two elements.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(None)` - If the iterator was empty
+    /// * `Ok(Some(item))` - If it yielded exactly one element
+    /// * `Err(MoreThanOne { first, second })` - If it yielded two or more
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::{IterExtra, prelude::MoreThanOne};
+    ///
+    /// let users = vec![(1, "ada"), (2, "grace"), (3, "ada")];
+    ///
+    /// let by_id = users.iter().filter(|(id, _)| *id == 2).at_most_one();
+    /// assert_eq!(by_id, Ok(Some(&(2, "grace"))));
+    ///
+    /// let missing = users.iter().filter(|(id, _)| *id == 9).at_most_one();
+    /// assert_eq!(missing, Ok(None));
+    ///
+    /// let by_name = users.iter().filter(|(_, name)| *name == "ada").at_most_one();
+    /// assert_eq!(by_name, Err(MoreThanOne { first: &(1, "ada"), second: &(3, "ada") }));
+    /// ```
+    fn at_most_one(mut self) -> Result<Option<Self::Item>, MoreThanOne<Self::Item>>
+    where
+        Self: Sized,
+    {
+        let Some(first) = self.next() else {
+            return Ok(None);
+        };
+        match self.next() {
+            None => Ok(Some(first)),
+            Some(second) => Err(MoreThanOne { first, second }),
+        }
+    }
+
+    /// Checks whether any element has a key equal to `target`.
+    ///
+    /// Stops at the first match.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_fn` - A function that extracts a key from each element
+    /// * `target` - The key to look for
+    ///
+    /// # Returns
+    ///
+    /// `true` if some element's key equals `target`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let files = vec!["main.rs", "lib.rs", "README.md"];
+    /// assert!(files.iter().contains_by_key(|f| f.rsplit('.').next(), Some("md")));
+    /// assert!(!files.iter().contains_by_key(|f| f.len(), 3));
+    /// ```
+    fn contains_by_key<K, F>(mut self, mut key_fn: F, target: K) -> bool
+    where
+        Self: Sized,
+        K: PartialEq,
+        F: FnMut(&Self::Item) -> K,
+    {
+        self.any(|item| key_fn(&item) == target)
+    }
+
+    /// Returns the first element whose key equals `target`.
+    ///
+    /// Stops at the first match.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_fn` - A function that extracts a key from each element
+    /// * `target` - The key to look for
+    ///
+    /// # Returns
+    ///
+    /// * `Some(item)` - The first element with a matching key
+    /// * `None` - If no element matches
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let users = vec![(17, "ada"), (42, "grace"), (99, "linus")];
+    /// assert_eq!(users.iter().find_by_key(|(id, _)| *id, 42), Some(&(42, "grace")));
+    /// assert_eq!(users.iter().find_by_key(|(id, _)| *id, 7), None);
+    /// ```
+    fn find_by_key<K, F>(mut self, mut key_fn: F, target: K) -> Option<Self::Item>
+    where
+        Self: Sized,
+        K: PartialEq,
+        F: FnMut(&Self::Item) -> K,
+    {
+        self.find(|item| key_fn(item) == target)
+    }
+
+    /// Counts the elements matching a predicate.
+    ///
+    /// Equivalent to `filter(predicate).count()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - A function that returns `true` for matching elements
+    ///
+    /// # Returns
+    ///
+    /// The number of matching elements
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let numbers = vec![1, 2, 3, 4, 5, 6];
+    /// assert_eq!(numbers.iter().count_if(|&&x| x % 3 == 0), 2);
+    /// ```
+    fn count_if<F>(self, mut predicate: F) -> usize
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> bool,
+    {
+        self.fold(0, |count, item| count + usize::from(predicate(&item)))
+    }
+
+    /// Counts the elements matching and not matching a predicate in a single pass.
+    ///
+    /// This replaces a `count_matches` that returned `(matching, total)`. The second field is
+    /// the number of non-matching elements, not the total; the total is
+    /// `matching + non_matching`.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - A function that returns `true` for matching elements
+    ///
+    /// # Returns
+    ///
+    /// A `(matching, non_matching)` tuple
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let latencies = vec![12, 340, 25, 980, 18];
+    /// let (fast, slow) = latencies.iter().count_partitioned(|&&ms| ms < 100);
+    /// assert_eq!((fast, slow), (3, 2));
+    /// assert_eq!(fast + slow, latencies.len());
+    /// ```
+    #[doc(alias = "count_matches")]
+    fn count_partitioned<F>(self, mut predicate: F) -> (usize, usize)
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> bool,
+    {
+        self.fold((0, 0), |(matching, non_matching), item| {
+            if predicate(&item) {
+                (matching + 1, non_matching)
+            } else {
+                (matching, non_matching + 1)
+            }
+        })
+    }
+
+    /// Sums values per key in a single pass.
+    ///
+    /// Each element is mapped to a key by `key_fn` and to a value by `value_fn`; values sharing
+    /// a key are added together in iteration order, starting from `V::default()`. This is
+    /// `fold_by_key` with a running sum as the accumulator.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_fn` - A function that extracts the group key from each element
+    /// * `value_fn` - A function that extracts the value to sum from each element
+    ///
+    /// # Returns
+    ///
+    /// A `HashMap` from each distinct key to the sum of its values
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let requests = vec![("api", 512), ("cdn", 2048), ("api", 256), ("cdn", 1024)];
+    /// let bytes = requests.iter().sum_by_key(|(host, _)| *host, |(_, size)| *size);
+    /// assert_eq!(bytes["api"], 768);
+    /// assert_eq!(bytes["cdn"], 3072);
+    /// ```
+    fn sum_by_key<K, V, F, G>(self, key_fn: F, mut value_fn: G) -> std::collections::HashMap<K, V>
+    where
+        Self: Sized,
+        K: std::cmp::Eq + std::hash::Hash,
+        V: Default + std::ops::AddAssign,
+        F: FnMut(&Self::Item) -> K,
+        G: FnMut(&Self::Item) -> V,
+    {
+        self.fold_by_key(key_fn, V::default, |sum, item| *sum += value_fn(&item))
+    }
+
+    /// Folds the elements of each group into an accumulator in a single pass.
+    ///
+    /// Each element is assigned a key by `key_fn`. The first time a key is seen its
+    /// accumulator is created by `init_fn`; every element is then folded into its key's
+    /// accumulator in place with `fold_fn`, in iteration order.
     ///
     /// # Arguments
     ///
-    /// * `key` - A function that extracts a key from each element for comparison
+    /// * `key_fn` - A function that extracts the group key from each element
+    /// * `init_fn` - A function that creates the initial accumulator for a new key
+    /// * `fold_fn` - A function that folds the next element of a group into its accumulator
     ///
     /// # Returns
     ///
-    /// * `Some(item)` - The element that produces the minimum key value
-    /// * `None` - If the iterator is empty
+    /// A `HashMap` from each distinct key to its final accumulator
     ///
     /// # Examples
     ///
     /// ```
     /// use iter_extra::IterExtra;
     ///
-    /// let numbers = vec![3.2, 1.5, 2.8, 0.9];
-    /// let min = numbers.iter().min_by_partial_key(|&x| x);
-    /// assert_eq!(min, Some(&0.9));
-    ///
-    /// // Works with NaN values
-    /// let with_nan = vec![1.0, f64::NAN, 2.0];
-    /// let min = with_nan.iter().min_by_partial_key(|&x| x);
-    /// assert_eq!(min, Some(&1.0));
+    /// let logs = vec![("db", 40), ("web", 12), ("db", 95), ("web", 30)];
+    /// let slowest = logs.iter().fold_by_key(
+    ///     |(service, _)| *service,
+    ///     || 0,
+    ///     |max, (_, ms)| *max = (*max).max(*ms),
+    /// );
+    /// assert_eq!(slowest["db"], 95);
+    /// assert_eq!(slowest["web"], 30);
     /// ```
-    fn min_by_partial_key<K: PartialOrd, F: FnMut(&Self::Item) -> K>(
+    fn fold_by_key<K, Acc, F, I, G>(
         self,
-        mut key: F,
-    ) -> Option<Self::Item>
+        mut key_fn: F,
+        mut init_fn: I,
+        mut fold_fn: G,
+    ) -> std::collections::HashMap<K, Acc>
     where
         Self: Sized,
+        K: std::cmp::Eq + std::hash::Hash,
+        F: FnMut(&Self::Item) -> K,
+        I: FnMut() -> Acc,
+        G: FnMut(&mut Acc, Self::Item),
     {
-        self.min_by(|x, y| {
-            key(x)
-                .partial_cmp(&key(y))
-                .unwrap_or(std::cmp::Ordering::Equal)
-        })
+        let mut groups = std::collections::HashMap::new();
+        for item in self {
+            let acc = groups.entry(key_fn(&item)).or_insert_with(&mut init_fn);
+            fold_fn(acc, item);
+        }
+        groups
     }
 
-    /// Returns the element that gives the maximum value from the specified function.
+    /// Returns the element with the maximum key within each group, in a single pass.
     ///
-    /// This method is similar to `Iterator::max_by_key`, but works with types that implement
-    /// `PartialOrd` instead of `Ord`. When the comparison returns `None` (indicating
-    /// incomparable values like NaN), it treats them as equal.
+    /// Elements are grouped by `group_fn` and compared within their group by `key_fn`, which is
+    /// called exactly once per element. Comparison follows `max_by_partial_key`: incomparable
+    /// keys (like NaN) are treated as equal, and if several elements of a group are equally
+    /// maximal, the last one is kept.
     ///
     /// # Arguments
     ///
-    /// * `key` - A function that extracts a key from each element for comparison
+    /// * `group_fn` - A function that extracts the group from each element
+    /// * `key_fn` - A function that extracts the key to compare within a group
     ///
     /// # Returns
     ///
-    /// * `Some(item)` - The element that produces the maximum key value
-    /// * `None` - If the iterator is empty
+    /// A `HashMap` from each distinct group to its maximal element
     ///
     /// # Examples
     ///
     /// ```
     /// use iter_extra::IterExtra;
     ///
-    /// let numbers = vec![3.2, 1.5, 2.8, 0.9];
-    /// let max = numbers.iter().max_by_partial_key(|&x| x);
-    /// assert_eq!(max, Some(&3.2));
-    ///
-    /// // Works with NaN values
-    /// let with_nan = vec![1.0, f64::NAN, 2.0];
-    /// let max = with_nan.iter().max_by_partial_key(|&x| x);
-    /// assert_eq!(max, Some(&2.0));
+    /// let readings = vec![
+    ///     ("kitchen", 21.5),
+    ///     ("garage", 12.0),
+    ///     ("kitchen", 23.1),
+    ///     ("garage", 9.4),
+    /// ];
+    /// let hottest = readings
+    ///     .iter()
+    ///     .max_by_partial_key_per_group(|(room, _)| *room, |(_, temp)| *temp);
+    /// assert_eq!(hottest["kitchen"], &("kitchen", 23.1));
+    /// assert_eq!(hottest["garage"], &("garage", 12.0));
     /// ```
-    fn max_by_partial_key<K: PartialOrd, F: FnMut(&Self::Item) -> K>(
+    fn max_by_partial_key_per_group<G, K, FG, FK>(
         self,
-        mut key: F,
-    ) -> Option<Self::Item>
+        mut group_fn: FG,
+        mut key_fn: FK,
+    ) -> std::collections::HashMap<G, Self::Item>
     where
         Self: Sized,
+        G: std::cmp::Eq + std::hash::Hash,
+        K: PartialOrd,
+        FG: FnMut(&Self::Item) -> G,
+        FK: FnMut(&Self::Item) -> K,
     {
-        self.max_by(|x, y| {
-            key(x)
-                .partial_cmp(&key(y))
-                .unwrap_or(std::cmp::Ordering::Equal)
-        })
+        let mut best: std::collections::HashMap<G, (K, Self::Item)> =
+            std::collections::HashMap::new();
+        for item in self {
+            let key = key_fn(&item);
+            match best.entry(group_fn(&item)) {
+                std::collections::hash_map::Entry::Occupied(mut entry) => {
+                    if key.partial_cmp(&entry.get().0) != Some(std::cmp::Ordering::Less) {
+                        entry.insert((key, item));
+                    }
+                }
+                std::collections::hash_map::Entry::Vacant(entry) => {
+                    entry.insert((key, item));
+                }
+            }
+        }
+        best.into_iter()
+            .map(|(group, (_, item))| (group, item))
+            .collect()
     }
 
-    fn collect_some_vec(self) -> Option<Vec<Self::Item>>
+    /// Returns an iterator over the indices of all elements matching a predicate.
+    ///
+    /// Unlike `Iterator::position`, which stops at the first match, this lazily yields every
+    /// matching index in ascending order.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - A function that returns `true` for matching elements
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields the zero-based index of each matching element
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let line = "a,b,,c";
+    /// let commas: Vec<usize> = line.chars().positions(|&c| c == ',').collect();
+    /// assert_eq!(commas, vec![1, 3, 4]);
+    /// ```
+    fn positions<F>(self, predicate: F) -> Positions<Self, F>
     where
         Self: Sized,
+        F: FnMut(&Self::Item) -> bool,
     {
-        Some(self.collect::<Vec<Self::Item>>()).filter(|v| !v.is_empty())
+        Positions::new(self, predicate)
     }
 
-    fn collect_ok_vec_or<E>(self, err: E) -> Result<Vec<Self::Item>, E>
+    /// Returns the index of the first element whose key equals `target`.
+    ///
+    /// Keys are compared with `PartialEq`, so a NaN target never matches. Stops at the first
+    /// match.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_fn` - A function that extracts a key from each element
+    /// * `target` - The key to look for
+    ///
+    /// # Returns
+    ///
+    /// * `Some(index)` - The zero-based index of the first element with a matching key
+    /// * `None` - If no element matches
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let points = vec![(0.0, 1.0), (0.5, 2.5), (1.0, 2.5)];
+    /// assert_eq!(points.iter().index_of_by_partial_key(|p| p.1, 2.5), Some(1));
+    /// assert_eq!(points.iter().index_of_by_partial_key(|p| p.0, f64::NAN), None);
+    /// ```
+    fn index_of_by_partial_key<K, F>(mut self, mut key_fn: F, target: K) -> Option<usize>
     where
         Self: Sized,
+        K: PartialEq,
+        F: FnMut(&Self::Item) -> K,
     {
-        Ok(self.collect::<Vec<Self::Item>>()).and_then(
-            |v| {
-                if v.is_empty() { Err(err) } else { Ok(v) }
-            },
-        )
+        self.position(|item| key_fn(&item) == target)
     }
 
-    fn collect_ok_vec_or_default<E: Default>(self) -> Result<Vec<Self::Item>, E>
+    /// Finds the first element that repeats an earlier one, stopping as soon as it is found.
+    ///
+    /// "First" means the repeat that occurs earliest in the iteration, so for `[1, 2, 2, 1]`
+    /// the result is the `2` at index 2, not the `1`.
+    ///
+    /// # Returns
+    ///
+    /// * `Some((first_index, second_index, item))` - The indices of the earlier occurrence and
+    ///   the repeat, together with the repeated element
+    /// * `None` - If all elements are distinct
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let ids = vec![7, 3, 9, 3, 7];
+    /// assert_eq!(ids.iter().find_first_duplicate(), Some((1, 3, &3)));
+    /// assert_eq!(vec![1, 2, 3].into_iter().find_first_duplicate(), None);
+    /// ```
+    fn find_first_duplicate(self) -> Option<(usize, usize, Self::Item)>
     where
         Self: Sized,
+        Self::Item: std::cmp::Eq + std::hash::Hash,
     {
-        Ok(self.collect::<Vec<Self::Item>>()).and_then(|v| {
-            if v.is_empty() {
-                Err(E::default())
-            } else {
-                Ok(v)
+        let mut seen = std::collections::HashMap::new();
+        for (index, item) in self.enumerate() {
+            if let Some(&first_index) = seen.get(&item) {
+                return Some((first_index, index, item));
             }
-        })
+            seen.insert(item, index);
+        }
+        None
     }
 
-    /// Returns an iterator that yields the distance from each element to its last occurrence.
+    /// Finds the first element whose key repeats the key of an earlier element, stopping as
+    /// soon as it is found.
     ///
-    /// For each element in the iterator, this method returns the number of elements between
-    /// the current element and the previous occurrence of the same element. If the element
-    /// hasn't appeared before, it returns the current index.
+    /// Similar to `find_first_duplicate`, but elements are compared by the key returned by
+    /// `key_fn`.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_fn` - A function that extracts a key from each element
     ///
     /// # Returns
     ///
-    /// An iterator that yields `usize` values representing the delta for each element
+    /// * `Some((first_index, second_index, item))` - The indices of the earlier occurrence and
+    ///   the repeat, together with the repeating element
+    /// * `None` - If all keys are distinct
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::IterExtra;
+    ///
+    /// let users = vec![
+    ///     ("ada", "ada@example.com"),
+    ///     ("bob", "bob@example.com"),
+    ///     ("eve", "ada@example.com"),
+    /// ];
+    /// let clash = users.iter().find_first_duplicate_by_key(|(_, email)| *email);
+    /// assert_eq!(clash, Some((0, 2, &("eve", "ada@example.com"))));
+    /// ```
+    fn find_first_duplicate_by_key<K, F>(self, mut key_fn: F) -> Option<(usize, usize, Self::Item)>
+    where
+        Self: Sized,
+        K: std::cmp::Eq + std::hash::Hash,
+        F: FnMut(&Self::Item) -> K,
+    {
+        let mut seen = std::collections::HashMap::new();
+        for (index, item) in self.enumerate() {
+            match seen.entry(key_fn(&item)) {
+                std::collections::hash_map::Entry::Occupied(entry) => {
+                    return Some((*entry.get(), index, item));
+                }
+                std::collections::hash_map::Entry::Vacant(entry) => {
+                    entry.insert(index);
+                }
+            }
+        }
+        None
+    }
+
+    /// Finds the first element that recurs, together with the gap to its previous occurrence.
+    ///
+    /// This is the early-exit counterpart of `deltas`: the gap is measured the same way (the
+    /// number of elements between the two occurrences), but iteration stops at the first
+    /// repeat instead of producing a delta for every element.
+    ///
+    /// # Returns
+    ///
+    /// * `Some((item, gap))` - The first recurring element and the number of elements between
+    ///   it and its previous occurrence
+    /// * `None` - If no element recurs
     ///
     /// # Examples
     ///
@@ -232,83 +8715,242 @@ pub trait IterExtra: Iterator {
     /// use iter_extra::IterExtra;
     ///
     /// let items = vec!['a', 'b', 'c', 'a', 'c'];
-    /// let deltas: Vec<usize> = items.into_iter().deltas().collect();
-    /// assert_eq!(deltas, vec![0, 1, 2, 2, 1]);
+    /// assert_eq!(items.into_iter().first_repeat_gap(), Some(('a', 2)));
+    ///
+    /// let doubled = vec![1, 2, 2, 3];
+    /// assert_eq!(doubled.into_iter().first_repeat_gap(), Some((2, 0)));
     /// ```
-    fn deltas(self) -> Deltas<Self>
+    fn first_repeat_gap(self) -> Option<(Self::Item, usize)>
     where
         Self: Sized,
         Self::Item: std::cmp::PartialEq,
     {
-        Deltas::new(self)
+        let mut deltas = self.deltas();
+        let mut index = 0;
+        loop {
+            let delta = deltas.next()?;
+            if delta < index {
+                let (item, _) = deltas.items.pop()?;
+                return Some((item, delta));
+            }
+            index += 1;
+        }
     }
 
-    /// Returns an iterator that yields the distance from each element to its last occurrence,
-    /// using a custom comparison function.
+    /// Returns an iterator that repeats the sequence exactly `n` times.
     ///
-    /// Similar to `deltas`, but uses a custom comparison function to determine element equality.
-    /// Two elements are considered equal when the comparison function returns `Ordering::Equal`.
+    /// Unlike `Iterator::cycle`, which repeats forever, the sequence is replayed from a clone
+    /// of the original iterator `n` times in total and then ends. With `n == 0` nothing is
+    /// yielded.
     ///
     /// # Arguments
     ///
-    /// * `cmp_fn` - A function that compares two elements and returns an `Ordering`
+    /// * `n` - The number of times to repeat the sequence
     ///
     /// # Returns
     ///
-    /// An iterator that yields `usize` values representing the delta for each element
+    /// An iterator that yields the elements of the sequence `n` times in a row
     ///
     /// # Examples
     ///
     /// ```
     /// use iter_extra::IterExtra;
     ///
-    /// let items = vec![1.1, 2.2, 3.3, 1.2, 2.1];
-    /// let deltas: Vec<usize> = items.into_iter()
-    ///     .deltas_by(|a, b| a.floor().total_cmp(&b.floor()))
-    ///     .collect();
-    /// assert_eq!(deltas, vec![0, 1, 2, 2, 2]);
+    /// let pattern: Vec<char> = "ab".chars().cycle_n(3).collect();
+    /// assert_eq!(pattern, vec!['a', 'b', 'a', 'b', 'a', 'b']);
+    ///
+    /// let tiles = [0, 1, 2].iter().cycle_n(2);
+    /// assert_eq!(tiles.size_hint(), (6, Some(6)));
     /// ```
-    fn deltas_by<F>(self, cmp_fn: F) -> DeltasBy<Self, F>
+    fn cycle_n(self, n: usize) -> CycleN<Self>
+    where
+        Self: Sized + Clone,
+    {
+        CycleN::new(self, n)
+    }
+}
+
+impl<I: Iterator<Item = T>, T> IterExtra for I {}
+
+/// Fallible versions of `IterExtra` methods for iterators of `Result<T, E>`.
+///
+/// Each method works on the `Ok` values and short-circuits at the first `Err`, returning it
+/// without consuming the rest of the iterator.
+pub trait TryIterExtra<T, E>: Iterator<Item = Result<T, E>> {
+    /// Computes `deltas` over the `Ok` values, stopping at the first error.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(deltas)` - The distance from each value to its previous occurrence
+    /// * `Err(err)` - The first error encountered
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::TryIterExtra;
+    ///
+    /// let ids = vec!["7", "3", "7"].into_iter().map(str::parse::<u32>);
+    /// assert_eq!(ids.try_deltas(), Ok(vec![0, 1, 1]));
+    ///
+    /// let broken = vec!["7", "?", "7"].into_iter().map(str::parse::<u32>);
+    /// assert!(broken.try_deltas().is_err());
+    /// ```
+    fn try_deltas(self) -> Result<Vec<usize>, E>
     where
         Self: Sized,
-        F: FnMut(&Self::Item, &Self::Item) -> std::cmp::Ordering,
+        T: PartialEq,
     {
-        DeltasBy::new(self, cmp_fn)
+        self.process_results(|values| values.deltas().collect())
     }
 
-    /// Returns an iterator that yields the distance from each element to its last occurrence,
-    /// comparing elements by a key extracted from each element.
+    /// Counts the occurrences of each distinct `Ok` value, stopping at the first error.
     ///
-    /// Similar to `deltas`, but determines element equality by comparing the keys extracted
-    /// by the provided key function. Two elements are considered equal if their keys are equal.
+    /// # Returns
+    ///
+    /// * `Ok(counts)` - A `HashMap` from each distinct value to its number of occurrences
+    /// * `Err(err)` - The first error encountered
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::TryIterExtra;
+    ///
+    /// let codes = vec!["200", "404", "200"].into_iter().map(str::parse::<u16>);
+    /// let counts = codes.try_counts().unwrap();
+    /// assert_eq!(counts[&200], 2);
+    /// assert_eq!(counts[&404], 1);
+    /// ```
+    fn try_counts(self) -> Result<std::collections::HashMap<T, usize>, E>
+    where
+        Self: Sized,
+        T: Eq + std::hash::Hash,
+    {
+        self.process_results(|values| values.counts())
+    }
+
+    /// Sorts the `Ok` values by key like `sorted_by_partial_key`, stopping at the first error.
     ///
     /// # Arguments
     ///
-    /// * `key_fn` - A function that extracts a key from each element for comparison
+    /// * `key_fn` - A function that extracts a key from each value for comparison
     ///
     /// # Returns
     ///
-    /// An iterator that yields `usize` values representing the delta for each element
+    /// * `Ok(sorted)` - The values in ascending key order
+    /// * `Err(err)` - The first error encountered
     ///
     /// # Examples
     ///
     /// ```
-    /// use iter_extra::IterExtra;
+    /// use iter_extra::TryIterExtra;
     ///
-    /// let items = vec!["apple", "banana", "apricot", "blueberry"];
-    /// let deltas: Vec<usize> = items.into_iter()
-    ///     .deltas_by_key(|s| s.chars().next())
-    ///     .collect();
-    /// assert_eq!(deltas, vec![0, 1, 1, 1]);
+    /// let readings = vec!["2.5", "-1", "0.75"].into_iter().map(str::parse::<f64>);
+    /// assert_eq!(readings.try_sorted_by_partial_key(|&x| x), Ok(vec![-1.0, 0.75, 2.5]));
     /// ```
-    fn deltas_by_key<K, F>(self, key_fn: F) -> DeltasByKey<Self, F>
+    fn try_sorted_by_partial_key<K, F>(self, key_fn: F) -> Result<Vec<T>, E>
     where
         Self: Sized,
-        F: FnMut(&Self::Item) -> K,
-        K: std::cmp::PartialEq,
+        K: PartialOrd,
+        F: FnMut(&T) -> K,
     {
-        DeltasByKey::new(self, key_fn)
+        self.process_results(|values| values.sorted_by_partial_key(key_fn).collect())
+    }
+
+    /// Folds the `Ok` values into an accumulator, stopping at the first error.
+    ///
+    /// # Arguments
+    ///
+    /// * `init` - The initial accumulator value
+    /// * `fold_fn` - A function that combines the accumulator with each `Ok` value
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(acc)` - The final accumulator, if no element was an `Err`
+    /// * `Err(err)` - The first error encountered
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::TryIterExtra;
+    ///
+    /// let sizes = vec!["120", "80", "300"].into_iter().map(str::parse::<u64>);
+    /// assert_eq!(sizes.fold_ok(0, |total, size| total + size), Ok(500));
+    ///
+    /// let broken = vec!["120", "eighty"].into_iter().map(str::parse::<u64>);
+    /// assert!(broken.fold_ok(0, |total, size| total + size).is_err());
+    /// ```
+    fn fold_ok<B, F>(mut self, init: B, mut fold_fn: F) -> Result<B, E>
+    where
+        Self: Sized,
+        F: FnMut(B, T) -> B,
+    {
+        self.try_fold(init, |acc, result| result.map(|value| fold_fn(acc, value)))
+    }
+
+    /// Returns the `Ok` value that gives the minimum key, stopping at the first error.
+    ///
+    /// Follows `min_by_partial_key`: incomparable keys (like NaN) are treated as equal, and
+    /// the first of several minimal values is returned. Values are compared as they arrive,
+    /// so nothing is buffered.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A function that extracts a key from each value for comparison
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(value))` - The value with the minimum key
+    /// * `Ok(None)` - If the iterator is empty
+    /// * `Err(err)` - The first error encountered
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::TryIterExtra;
+    ///
+    /// let parsed = vec!["3.5", "-0.25", "1"].into_iter().map(str::parse::<f64>);
+    /// assert_eq!(parsed.try_min_by_partial_key(|&x| x), Ok(Some(-0.25)));
+    /// ```
+    fn try_min_by_partial_key<K, F>(self, key: F) -> Result<Option<T>, E>
+    where
+        Self: Sized,
+        K: PartialOrd,
+        F: FnMut(&T) -> K,
+    {
+        self.process_results(|values| values.min_by_partial_key(key))
+    }
+
+    /// Returns the `Ok` value that gives the maximum key, stopping at the first error.
+    ///
+    /// Follows `max_by_partial_key`: incomparable keys (like NaN) are treated as equal, and
+    /// the last of several maximal values is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A function that extracts a key from each value for comparison
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(value))` - The value with the maximum key
+    /// * `Ok(None)` - If the iterator is empty
+    /// * `Err(err)` - The first error encountered
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iter_extra::TryIterExtra;
+    ///
+    /// let parsed = vec!["3.5", "nope", "9"].into_iter().map(str::parse::<f64>);
+    /// assert!(parsed.try_max_by_partial_key(|&x| x).is_err());
+    /// ```
+    fn try_max_by_partial_key<K, F>(self, key: F) -> Result<Option<T>, E>
+    where
+        Self: Sized,
+        K: PartialOrd,
+        F: FnMut(&T) -> K,
+    {
+        self.process_results(|values| values.max_by_partial_key(key))
     }
 }
 
-impl<I: Iterator<Item = T>, T> IterExtra for I {}
+impl<I: Iterator<Item = Result<T, E>>, T, E> TryIterExtra<T, E> for I {}
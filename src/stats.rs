@@ -0,0 +1,170 @@
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Summary {
+    /// Number of non-NaN values.
+    pub count: usize,
+    /// Number of NaN values that were skipped.
+    pub nan_count: usize,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    /// Population variance (divided by `count`).
+    pub variance: f64,
+    /// Population standard deviation.
+    pub stddev: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinearFit {
+    pub slope: f64,
+    pub intercept: f64,
+    /// Coefficient of determination, `1.0` when all `y` values are equal.
+    pub r_squared: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    /// Bucket boundaries; bucket `i` covers `edges[i]..edges[i + 1]`, the last one inclusive.
+    pub edges: Vec<f64>,
+    pub counts: Vec<usize>,
+    /// Number of values below the first edge.
+    pub below: usize,
+    /// Number of values above the last edge.
+    pub above: usize,
+    pub nan_count: usize,
+}
+
+impl Histogram {
+    pub(crate) fn with_edges(edges: Vec<f64>) -> Self {
+        assert!(edges.len() >= 2, "histogram needs at least two edges");
+        assert!(
+            edges.windows(2).all(|w| w[0] < w[1]),
+            "histogram edges must be strictly increasing"
+        );
+        Histogram {
+            counts: vec![0; edges.len() - 1],
+            edges,
+            below: 0,
+            above: 0,
+            nan_count: 0,
+        }
+    }
+
+    pub(crate) fn add(&mut self, value: f64) {
+        let last = self.edges[self.edges.len() - 1];
+        if value.is_nan() {
+            self.nan_count += 1;
+        } else if value < self.edges[0] {
+            self.below += 1;
+        } else if value > last {
+            self.above += 1;
+        } else {
+            let bucket = self.edges.partition_point(|&edge| edge <= value) - 1;
+            self.counts[bucket.min(self.edges.len() - 2)] += 1;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Welford {
+    pub(crate) count: usize,
+    mean: f64,
+    m2: f64,
+}
+
+impl Welford {
+    pub(crate) fn push(&mut self, value: f64) {
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+    }
+
+    pub(crate) fn mean(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.mean)
+    }
+
+    pub(crate) fn variance(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.m2 / self.count as f64)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct NeumaierSum {
+    sum: f64,
+    compensation: f64,
+}
+
+impl NeumaierSum {
+    pub(crate) fn push(&mut self, value: f64) {
+        let total = self.sum + value;
+        // Once the sum is infinite or NaN there is no rounding error left to track, and the
+        // compensation terms would only turn an infinite sum into NaN.
+        if total.is_finite() {
+            self.compensation += if self.sum.abs() >= value.abs() {
+                (self.sum - total) + value
+            } else {
+                (value - total) + self.sum
+            };
+        }
+        self.sum = total;
+    }
+
+    pub(crate) fn total(&self) -> f64 {
+        if self.sum.is_finite() {
+            self.sum + self.compensation
+        } else {
+            self.sum
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct CoMoments {
+    pub(crate) count: usize,
+    pub(crate) mean_x: f64,
+    pub(crate) mean_y: f64,
+    pub(crate) m2_x: f64,
+    pub(crate) m2_y: f64,
+    pub(crate) c_xy: f64,
+}
+
+impl CoMoments {
+    pub(crate) fn push(&mut self, x: f64, y: f64) {
+        self.count += 1;
+        let n = self.count as f64;
+        let delta_x = x - self.mean_x;
+        let delta_y = y - self.mean_y;
+        self.mean_x += delta_x / n;
+        self.mean_y += delta_y / n;
+        self.m2_x += delta_x * (x - self.mean_x);
+        self.m2_y += delta_y * (y - self.mean_y);
+        self.c_xy += delta_x * (y - self.mean_y);
+    }
+
+    pub(crate) fn covariance(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.c_xy / self.count as f64)
+    }
+
+    pub(crate) fn correlation(&self) -> Option<f64> {
+        let denominator = (self.m2_x * self.m2_y).sqrt();
+        (self.count > 0 && denominator > 0.0).then(|| self.c_xy / denominator)
+    }
+
+    pub(crate) fn linear_fit(&self) -> Option<LinearFit> {
+        if self.count == 0 || self.m2_x <= 0.0 {
+            return None;
+        }
+
+        let slope = self.c_xy / self.m2_x;
+        let r_squared = if self.m2_y > 0.0 {
+            (self.c_xy * self.c_xy) / (self.m2_x * self.m2_y)
+        } else {
+            1.0
+        };
+        Some(LinearFit {
+            slope,
+            intercept: self.mean_y - slope * self.mean_x,
+            r_squared,
+        })
+    }
+}